@@ -3,117 +3,190 @@ use std::collections::BTreeMap;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Order, Response, StdResult, Uint128,
+    from_json, to_json_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, Timestamp, Uint128, Uint256,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
 use ethabi::{Address, Contract, Function, Param, ParamType, StateMutability, Token, Uint};
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::error::ContractError;
-use crate::error::ContractError::MigrationFailed;
+use crate::events::{response, PusdEvent};
+use crate::migrations;
 use crate::msg::{
-    BalanceResponse, ChainSettingInfo, ChangeAdminMsg, ExecuteJob, ExecuteMsg, InstantiateMsg,
-    MigrateMsg, PalomaMsg, QueryMsg, SetErc20ToDenom,
+    BalanceResponse, BridgeResponse, CancelTx, ChainInfoResponse, ChainSettingInfo, ChangeAdminMsg,
+    ComplianceStatusResponse, EvmQuery, ExecuteJob, ExecuteMsg, ExpiredWithdrawalsResponse,
+    ExportCounters, ExportCursor, ExportEntry, ExportPage, InstantiateMsg, JobResponse, MigrateMsg,
+    PalomaMsg, PalomaQuery, QueryMsg, ReconciliationResponse, SchedulerMsg, SchedulerQuery, SendTx,
+    SetErc20ToDenom, SkywayMsg, SolvencyResponse, StateExport, TokenFactoryMsg, UserQuotaResponse,
+};
+use crate::state::{
+    default_decimals, Accounting, AdminAction, BridgeInfo, BurnInfo, ChainSetting,
+    ChainSettingAction, ChainSettingChange, ComplianceList, ExpiredWithdrawal, ImportProgress,
+    MintRequest, QueuedAdminAction, QuotaUsage, Redemption, State, Transport, WithdrawStatus,
+    ACCOUNTING, ALLOWANCES, ALLOWLIST, BRIDGES, BRIDGE_HISTORY, CHAIN_ACCOUNTING, CHAIN_SETTINGS,
+    CHAIN_SETTING_HISTORY, CHAIN_WITHDRAWALS, EXPIRED_WITHDRAWALS, IMPORT_PROGRESS,
+    LAST_ADMIN_ACTION_ID, LAST_MINT_REQUEST_ID, LAST_REDEMPTION_ID, MINT_APPROVAL_CONFIG,
+    MINT_REQUESTS, QUEUED_ADMIN_ACTIONS, QUOTA_BUCKET, QUOTA_BUCKETS, QUOTA_USAGE,
+    RECIPIENT_DENYLIST, REDEMPTIONS, REDEMPTION_REFERENCES, REDEMPTION_TOTALS, REMOTE_CONFIGS,
+    SENDER_DENYLIST, STATE, WITHDRAW_LIST,
 };
-use crate::state::{BurnInfo, State, CHAIN_SETTINGS, STATE, WITHDRAW_LIST};
 use std::str::FromStr;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:pusd-manager-cw";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Format of the ExportState bundle
+const EXPORT_VERSION: u32 = 1;
+
+// Reply of a Skyway send_tx, carrying the withdraw nonce as payload
+const SKYWAY_SEND_REPLY_ID: u64 = 1;
+
+// Batch size of RefundExpired and RetryBatch
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+// Withdrawals a RefundExpired or RetryBatch call looks at, whether it takes them or not
+const MAX_SCAN: usize = 100;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut<PalomaQuery>,
+    env: Env,
     msg: MigrateMsg,
 ) -> Result<Response<PalomaMsg>, ContractError> {
-    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
-    struct OldState {
-        pub retry_delay: u64,
-        pub owner: Addr,
-        pub denom: String,
-        pub last_nonce: u64,
-    }
-
-    let Some(old_state) = deps.storage.get(b"state") else {
-        return Err(MigrationFailed {});
-    };
-    let old_state: OldState = cosmwasm_std::from_json(&old_state)?;
-    let new_state = State {
-        retry_delay: old_state.retry_delay,
-        owner: old_state.owner,
-        minter: msg.minter.clone(),
-        denom: old_state.denom,
-        last_nonce: old_state.last_nonce,
-    };
+    let (stored_version, applied) =
+        migrations::migrate(deps.branch(), &env, &msg, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    let new_data = cosmwasm_std::to_json_vec(&new_state)?;
-    deps.storage.set(b"state", &new_data);
+    let mut messages = vec![];
+    if let Some(minter) = msg.minter.clone() {
+        let state = STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
+            state.minter = minter.clone();
+            Ok(state)
+        })?;
+        messages.push(CosmosMsg::Custom(PalomaMsg::TokenFactoryMsg(
+            TokenFactoryMsg::ChangeAdmin(ChangeAdminMsg {
+                denom: state.denom,
+                new_admin_address: minter.to_string(),
+            }),
+        )));
+    }
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::new()
-        .add_message(CosmosMsg::Custom(PalomaMsg::TokenFactoryMsg {
-            change_admin: ChangeAdminMsg {
-                denom: new_state.denom.clone(),
-                new_admin_address: msg.minter.to_string(),
-            },
-        }))
-        .add_attribute("action", "migrate")
-        .add_attribute("version", CONTRACT_VERSION))
+    Ok(response(PusdEvent::Migrate {
+        from_version: stored_version,
+        version: CONTRACT_VERSION.to_string(),
+        steps: applied,
+        minter: msg.minter,
+    })
+    .add_messages(messages))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    deps: DepsMut<PalomaQuery>,
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response<PalomaMsg>, ContractError> {
     assert!(!info.funds.is_empty(), "Insufficient funds");
+    if let Some(export) = msg.import {
+        return import_state(deps, &info, export);
+    }
     let state = State {
         retry_delay: msg.retry_delay,
         owner: info.sender.clone(),
         minter: msg.minter,
         denom: msg.denom,
         last_nonce: 0,
+        decimals: msg.decimals.unwrap_or_else(default_decimals),
+        timelock_delay: msg.timelock_delay.unwrap_or(0),
     };
     STATE.save(deps.storage, &state)?;
-    Ok(Response::new()
-        .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender)
-        .add_attribute("action", "create_pusd")
-        .add_attribute("denom", &state.denom))
+    ACCOUNTING.save(deps.storage, &Accounting::default())?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(response(PusdEvent::Instantiate {
+        owner: state.owner,
+        minter: state.minter,
+        denom: state.denom,
+        decimals: state.decimals,
+        retry_delay: state.retry_delay,
+    }))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    mut deps: DepsMut<PalomaQuery>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response<PalomaMsg>, ContractError> {
+    if IMPORT_PROGRESS.exists(deps.storage) {
+        assert!(
+            matches!(
+                msg,
+                ExecuteMsg::ImportEntries { .. } | ExecuteMsg::FinalizeImport {}
+            ),
+            "Import in progress"
+        );
+    }
     match msg {
         ExecuteMsg::RegisterChain {
             chain_id,
             chain_setting,
         } => {
-            // ACTION: Implement RegisterJob
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
             assert!(
-                info.sender == STATE.load(deps.storage)?.owner,
-                "Unauthorized"
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
             );
-            assert!(!chain_id.is_empty(), "Chain ID cannot be empty");
-            assert!(!chain_setting.job_id.is_empty(), "Job ID cannot be empty");
-            CHAIN_SETTINGS.save(deps.storage, chain_id.clone(), &chain_setting)?;
-            Ok(Response::new().add_attributes(vec![
-                ("action", "register_job"),
-                ("chain_id", &chain_id),
-                ("job_id", &chain_setting.job_id),
-                ("minimum_amount", &chain_setting.minimum_amount.to_string()),
-            ]))
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::RegisterChain {
+                    chain_id,
+                    chain_setting,
+                },
+            )
+        }
+        ExecuteMsg::UpdateChain {
+            chain_id,
+            chain_setting,
+        } => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            assert!(
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::UpdateChain {
+                    chain_id,
+                    chain_setting,
+                },
+            )
+        }
+        ExecuteMsg::DeregisterChain { chain_id, force } => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            assert!(
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::DeregisterChain { chain_id, force },
+            )
         }
         ExecuteMsg::SetBridge {
             chain_reference_id,
@@ -123,16 +196,40 @@ pub fn execute(
                 info.sender == STATE.load(deps.storage)?.owner,
                 "Unauthorized"
             );
+            assert!(
+                CHAIN_SETTINGS.has(deps.storage, chain_reference_id.clone()),
+                "Chain not registered"
+            );
+            assert!(
+                Address::from_str(erc20_address.as_str()).is_ok(),
+                "Invalid ERC20 address"
+            );
+            let bridge_info = BridgeInfo {
+                erc20_address: erc20_address.clone(),
+                timestamp: env.block.time,
+            };
+            let next_index =
+                next_history_index(deps.storage, &BRIDGE_HISTORY, &chain_reference_id)?;
+            BRIDGE_HISTORY.save(
+                deps.storage,
+                (chain_reference_id.clone(), next_index),
+                &bridge_info,
+            )?;
+            BRIDGES.save(deps.storage, chain_reference_id.clone(), &bridge_info)?;
+
             let token_denom = STATE.load(deps.storage)?.denom.clone();
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SkywayMsg {
-                    set_erc20_to_denom: SetErc20ToDenom {
-                        erc20_address,
-                        token_denom,
-                        chain_reference_id,
-                    },
-                }))
-                .add_attribute("action", "set_bridge"))
+            Ok(response(PusdEvent::SetBridge {
+                chain_reference_id: chain_reference_id.clone(),
+                erc20_address: erc20_address.clone(),
+                denom: token_denom.clone(),
+            })
+            .add_message(CosmosMsg::Custom(PalomaMsg::SkywayMsg(
+                SkywayMsg::SetErc20ToDenom(SetErc20ToDenom {
+                    erc20_address,
+                    token_denom,
+                    chain_reference_id,
+                }),
+            ))))
         }
         ExecuteMsg::MintPusd { recipient, amount } => {
             // ACTION: Implement MintPusd
@@ -142,119 +239,320 @@ pub fn execute(
             );
 
             assert!(!amount.is_zero(), "Amount must be greater than 0");
+            assert_sender_allowed(deps.storage, &recipient);
+            if let Some(config) = MINT_APPROVAL_CONFIG.may_load(deps.storage)? {
+                assert!(
+                    amount < config.minimum_amount,
+                    "Mint requires approval, use ProposeMint"
+                );
+            }
+            let denom = STATE.load(deps.storage)?.denom;
+            assert_inventory(deps.as_ref(), &env, &denom, amount)?;
+            update_accounting(deps.storage, |accounting| {
+                accounting.total_minted += amount;
+                Ok(())
+            })?;
 
-            Ok(Response::new()
-                .add_message(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: recipient.to_string(),
-                    amount: vec![Coin {
-                        denom: STATE.load(deps.storage)?.denom,
-                        amount,
-                    }],
-                }))
-                .add_attributes(vec![
-                    ("action", "mint_pusd"),
-                    ("recipient", recipient.as_str()),
-                    ("amount", &amount.to_string()),
-                ]))
+            Ok(response(PusdEvent::Mint {
+                recipient: recipient.clone(),
+                amount,
+                denom: denom.clone(),
+            })
+            .add_message(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin { denom, amount }],
+            })))
+        }
+        ExecuteMsg::UpdateMintApprovalConfig { config } => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            assert!(
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::UpdateMintApprovalConfig { config },
+            )
+        }
+        ExecuteMsg::ProposeMint { recipient, amount } => {
+            let config = MINT_APPROVAL_CONFIG.load(deps.storage)?;
+            assert!(
+                config.proposers.contains(&info.sender)
+                    || info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            assert!(!amount.is_zero(), "Amount must be greater than 0");
+            assert_sender_allowed(deps.storage, &recipient);
+            let id = LAST_MINT_REQUEST_ID.may_load(deps.storage)?.unwrap_or(0) + 1;
+            LAST_MINT_REQUEST_ID.save(deps.storage, &id)?;
+            let mut mint_request = MintRequest {
+                recipient: recipient.clone(),
+                amount,
+                proposer: info.sender.clone(),
+                approvals: vec![],
+                created_at: env.block.time,
+                expires_at: env.block.time.plus_seconds(config.expiry),
+                executed: false,
+            };
+            // A proposer who is also an approver approves their own request
+            if config.approvers.contains(&info.sender) {
+                mint_request.approvals.push(info.sender.clone());
+            }
+            mint_request.executed = mint_request.approvals.len() as u32 >= config.threshold;
+            MINT_REQUESTS.save(deps.storage, id, &mint_request)?;
+            let response = response(PusdEvent::ProposeMint {
+                id,
+                proposer: info.sender,
+                recipient,
+                amount,
+                executed: mint_request.executed,
+            });
+            if mint_request.executed {
+                return execute_mint_request(deps, &env, response, &mint_request);
+            }
+            Ok(response)
+        }
+        ExecuteMsg::ApproveMint { id } => {
+            let config = MINT_APPROVAL_CONFIG.load(deps.storage)?;
+            assert!(config.approvers.contains(&info.sender), "Unauthorized");
+            let mut mint_request = MINT_REQUESTS.load(deps.storage, id)?;
+            assert!(!mint_request.executed, "Mint request already executed");
+            assert!(
+                env.block.time < mint_request.expires_at,
+                "Mint request expired"
+            );
+            assert!(
+                !mint_request.approvals.contains(&info.sender),
+                "Already approved"
+            );
+            mint_request.approvals.push(info.sender.clone());
+            mint_request.executed = mint_request.approvals.len() as u32 >= config.threshold;
+            MINT_REQUESTS.save(deps.storage, id, &mint_request)?;
+            let response = response(PusdEvent::ApproveMint {
+                id,
+                approver: info.sender,
+                approvals: mint_request.approvals.len() as u32,
+                executed: mint_request.executed,
+            });
+            if mint_request.executed {
+                return execute_mint_request(deps, &env, response, &mint_request);
+            }
+            Ok(response)
+        }
+        ExecuteMsg::CancelMint { id } => {
+            let mint_request = MINT_REQUESTS.load(deps.storage, id)?;
+            assert!(
+                info.sender == mint_request.proposer
+                    || info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            assert!(!mint_request.executed, "Mint request already executed");
+            MINT_REQUESTS.remove(deps.storage, id);
+            Ok(response(PusdEvent::CancelMint { id }))
         }
         ExecuteMsg::Withdraw {
             chain_id,
             recipient,
+            on_behalf_of,
+            refund_address,
         } => {
-            let nonce = STATE.load(deps.storage)?.last_nonce + 1;
+            let state = STATE.load(deps.storage)?;
+            let refund_address = refund_address
+                .map(|refund_address| deps.api.addr_validate(refund_address.as_str()))
+                .transpose()?;
+            let on_behalf_of = on_behalf_of
+                .map(|on_behalf_of| deps.api.addr_validate(&on_behalf_of))
+                .transpose()?;
+            let burner = on_behalf_of.clone().unwrap_or_else(|| info.sender.clone());
+            let nonce = state.last_nonce + 1;
 
             let mut amount: Uint128 = Uint128::zero();
             info.funds.iter().for_each(|coin: &Coin| {
-                if coin.denom == STATE.load(deps.storage).unwrap().denom {
+                if coin.denom == state.denom {
                     amount = coin.amount;
                 }
             });
             let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            assert!(
+                Address::from_str(recipient.as_str()).is_ok(),
+                "Invalid EVM address"
+            );
+            if chain_setting.transport == Transport::Skyway {
+                assert!(
+                    BRIDGES.has(deps.storage, chain_id.clone()),
+                    "Bridge not set"
+                );
+            }
+            assert!(
+                chain_active(deps.as_ref(), &chain_id)?,
+                "Chain is not active"
+            );
+            assert_compliant(deps.storage, &chain_setting, &burner, &recipient);
+            if on_behalf_of.is_some() {
+                assert_compliant(deps.storage, &chain_setting, &info.sender, &recipient);
+            }
             assert!(
                 amount > chain_setting.minimum_amount,
                 "Amount must be greater than minimum amount"
             );
+            let remote_decimals = chain_setting.decimals.unwrap_or(state.decimals);
+            let (remote_amount, dust) = to_remote_amount(amount, state.decimals, remote_decimals)?;
+            assert!(
+                !remote_amount.is_zero(),
+                "Amount too small for remote decimals"
+            );
+            let amount = amount - dust;
+            consume_quota(
+                deps.storage,
+                &env,
+                &chain_id,
+                &chain_setting,
+                &burner,
+                amount,
+            )?;
+            if on_behalf_of.is_some() {
+                let key = (
+                    burner.to_string(),
+                    info.sender.to_string(),
+                    chain_id.clone(),
+                );
+                let allowance = ALLOWANCES
+                    .may_load(deps.storage, key.clone())?
+                    .unwrap_or_default();
+                assert!(allowance >= amount, "Allowance exceeded");
+                ALLOWANCES.save(deps.storage, key, &(allowance - amount))?;
+            }
             let burn_info = BurnInfo {
                 chain_id: chain_id.clone(),
-                burner: info.sender.clone(),
+                burner: burner.clone(),
                 recipient: recipient.clone(),
-                amount: info.funds[0].amount.u128(),
+                amount: amount.u128(),
                 timestamp: env.block.time,
+                remote_amount: Some(remote_amount),
+                operator: on_behalf_of.map(|_| info.sender.clone()),
+                refund_address: refund_address.clone(),
+                created_at: Some(env.block.time),
+                attempts: 0,
+                status: WithdrawStatus::Pending,
+                transport: chain_setting.transport.clone(),
+                skyway_tx_id: None,
             };
 
             WITHDRAW_LIST.save(deps.storage, nonce, &burn_info)?;
+            CHAIN_WITHDRAWALS.save(deps.storage, (chain_id.clone(), nonce), &Empty {})?;
+            update_accounting(deps.storage, |accounting| {
+                match burn_info.transport {
+                    Transport::Compass => accounting.total_escrowed += amount,
+                    Transport::Skyway => accounting.total_in_transit += amount,
+                }
+                Ok(())
+            })?;
+            CHAIN_ACCOUNTING.update(
+                deps.storage,
+                chain_id.clone(),
+                |chain_accounting| -> StdResult<_> {
+                    let mut chain_accounting = chain_accounting.unwrap_or_default();
+                    chain_accounting.total_withdrawn += amount;
+                    Ok(chain_accounting)
+                },
+            )?;
 
             STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
                 state.last_nonce = nonce;
                 Ok(state)
             })?;
 
-            #[allow(deprecated)]
-            let contract: Contract = Contract {
-                constructor: None,
-                functions: BTreeMap::from_iter(vec![(
-                    "withdraw".to_string(),
-                    vec![Function {
-                        name: "withdraw".to_string(),
-                        inputs: vec![
-                            Param {
-                                name: "sender".to_string(),
-                                kind: ParamType::FixedBytes(32),
-                                internal_type: None,
-                            },
-                            Param {
-                                name: "recipient".to_string(),
-                                kind: ParamType::Address,
-                                internal_type: None,
-                            },
-                            Param {
-                                name: "amount".to_string(),
-                                kind: ParamType::Uint(256),
-                                internal_type: None,
-                            },
-                            Param {
-                                name: "nonce".to_string(),
-                                kind: ParamType::Uint(256),
-                                internal_type: None,
-                            },
-                        ],
-                        outputs: Vec::new(),
-                        constant: None,
-                        state_mutability: StateMutability::NonPayable,
+            let mut response = response(PusdEvent::Withdraw {
+                nonce,
+                chain_id: chain_id.clone(),
+                burner: burner.clone(),
+                recipient: recipient.clone(),
+                amount,
+                remote_amount,
+                dust,
+                denom: state.denom.clone(),
+                transport: burn_info.transport.clone(),
+                operator: burn_info.operator,
+                refund_address: refund_address.clone(),
+            });
+            if !dust.is_zero() {
+                // Refund the part that can't be represented with the remote decimals, like
+                // a cancelled withdraw
+                response = response.add_message(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: refund_address.unwrap_or_else(|| burner.clone()).to_string(),
+                    amount: vec![Coin {
+                        denom: state.denom.clone(),
+                        amount: dust,
                     }],
-                )]),
-                events: BTreeMap::new(),
-                errors: BTreeMap::new(),
-                receive: false,
-                fallback: false,
-            };
-            let canonical_addr: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
-            let tokens = &[
-                Token::FixedBytes(canonical_addr.as_slice().to_vec()),
-                Token::Address(Address::from_str(recipient.as_str()).unwrap()),
-                Token::Uint(Uint::from_big_endian(&amount.to_be_bytes())),
-                Token::Uint(Uint::from_big_endian(&nonce.to_be_bytes())),
-            ];
-
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                    execute_job: ExecuteJob {
-                        job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                        payload: Binary::new(
-                            contract
-                                .function("withdraw")
-                                .unwrap()
-                                .encode_input(tokens.as_slice())
-                                .unwrap(),
-                        ),
-                    },
-                }))
-                .add_attributes(vec![
-                    ("action", "withdraw"),
-                    ("chain_id", &chain_id),
-                    ("recipient", recipient.as_str()),
-                    ("nonce", &nonce.to_string()),
-                ]))
+                }));
+            }
+            match burn_info.transport {
+                Transport::Compass => {
+                    let payload =
+                        withdraw_payload(deps.as_ref(), &burner, &recipient, remote_amount, nonce)?;
+                    Ok(response.add_message(scheduler_job(
+                        deps.as_ref(),
+                        &chain_setting,
+                        JobKind::Withdraw,
+                        payload,
+                    )))
+                }
+                Transport::Skyway => {
+                    // The transaction id needed to cancel the transfer is read from the reply
+                    let send_tx =
+                        CosmosMsg::Custom(PalomaMsg::SkywayMsg(SkywayMsg::SendTx(SendTx {
+                            remote_chain_destination_address: recipient,
+                            amount: format!("{}{}", amount, state.denom),
+                            chain_reference_id: chain_id,
+                        })));
+                    Ok(response.add_submessage(
+                        SubMsg::reply_on_success(send_tx, SKYWAY_SEND_REPLY_ID)
+                            .with_payload(to_json_binary(&nonce)?),
+                    ))
+                }
+            }
+        }
+        ExecuteMsg::GrantAllowance {
+            spender,
+            chain_id,
+            amount,
+        } => {
+            let spender = deps.api.addr_validate(&spender)?;
+            assert!(spender != info.sender, "Cannot grant allowance to self");
+            ALLOWANCES.save(
+                deps.storage,
+                (
+                    info.sender.to_string(),
+                    spender.to_string(),
+                    chain_id.clone(),
+                ),
+                &amount,
+            )?;
+            Ok(response(PusdEvent::GrantAllowance {
+                owner: info.sender,
+                spender,
+                chain_id,
+                amount,
+            }))
+        }
+        ExecuteMsg::RevokeAllowance { spender, chain_id } => {
+            let spender = deps.api.addr_validate(&spender)?;
+            ALLOWANCES.remove(
+                deps.storage,
+                (
+                    info.sender.to_string(),
+                    spender.to_string(),
+                    chain_id.clone(),
+                ),
+            );
+            Ok(response(PusdEvent::RevokeAllowance {
+                owner: info.sender,
+                spender,
+                chain_id,
+            }))
         }
         ExecuteMsg::BurnPusd { nonce } => {
             // ACTION: Implement BurnPusd
@@ -263,160 +561,439 @@ pub fn execute(
                 "Unauthorized"
             );
 
+            let burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
             WITHDRAW_LIST.remove(deps.storage, nonce);
+            CHAIN_WITHDRAWALS.remove(deps.storage, (burn_info.chain_id.clone(), nonce));
+            let amount = Uint128::from(burn_info.amount);
+            update_accounting(deps.storage, |accounting| {
+                match burn_info.transport {
+                    Transport::Compass => {
+                        accounting.total_escrowed = accounting.total_escrowed.checked_sub(amount)?
+                    }
+                    Transport::Skyway => {
+                        accounting.total_in_transit =
+                            accounting.total_in_transit.checked_sub(amount)?
+                    }
+                }
+                accounting.total_burned += amount;
+                Ok(())
+            })?;
+            CHAIN_ACCOUNTING.update(
+                deps.storage,
+                burn_info.chain_id.clone(),
+                |chain_accounting| -> StdResult<_> {
+                    let mut chain_accounting = chain_accounting.unwrap_or_default();
+                    chain_accounting.total_burned += amount;
+                    Ok(chain_accounting)
+                },
+            )?;
 
-            Ok(Response::new()
-                .add_attributes(vec![("action", "burn_pusd"), ("nonce", &nonce.to_string())]))
+            Ok(response(PusdEvent::Burn {
+                nonce,
+                chain_id: burn_info.chain_id,
+                burner: burn_info.burner,
+                amount,
+                denom: STATE.load(deps.storage)?.denom,
+            }))
         }
         ExecuteMsg::ReWithdraw { nonce } => {
             // ACTION: Implement ReWithdraw
             let burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
             assert!(
-                burn_info
-                    .timestamp
-                    .plus_seconds(STATE.load(deps.storage)?.retry_delay)
-                    < env.block.time,
+                burn_info.transport == Transport::Compass,
+                "Withdraw is relayed by Skyway"
+            );
+            assert!(
+                burn_info.status == WithdrawStatus::Pending,
+                "Withdraw needs review"
+            );
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, burn_info.chain_id.clone())?;
+            let delay = retry_delay(
+                &STATE.load(deps.storage)?,
+                &chain_setting,
+                burn_info.attempts,
+            );
+            assert!(
+                burn_info.timestamp.plus_seconds(delay) < env.block.time,
                 "Retry delay not reached"
             );
-
-            #[allow(deprecated)]
-            let contract: Contract = Contract {
-                constructor: None,
-                functions: BTreeMap::from_iter(vec![(
-                    "withdraw".to_string(),
-                    vec![Function {
-                        name: "withdraw".to_string(),
-                        inputs: vec![
-                            Param {
-                                name: "sender".to_string(),
-                                kind: ParamType::FixedBytes(32),
-                                internal_type: None,
-                            },
-                            Param {
-                                name: "recipient".to_string(),
-                                kind: ParamType::Address,
-                                internal_type: None,
-                            },
-                            Param {
-                                name: "amount".to_string(),
-                                kind: ParamType::Uint(256),
-                                internal_type: None,
-                            },
-                            Param {
-                                name: "nonce".to_string(),
-                                kind: ParamType::Uint(256),
-                                internal_type: None,
-                            },
-                        ],
-                        outputs: Vec::new(),
-                        constant: None,
-                        state_mutability: StateMutability::NonPayable,
-                    }],
-                )]),
-                events: BTreeMap::new(),
-                errors: BTreeMap::new(),
-                receive: false,
-                fallback: false,
-            };
-            let tokens = &[
-                Token::FixedBytes(
-                    deps.api
-                        .addr_canonicalize(info.sender.as_str())?
-                        .as_slice()
-                        .to_vec(),
-                ),
-                Token::Address(Address::from_str(burn_info.recipient.as_str()).unwrap()),
-                Token::Uint(Uint::from_big_endian(&burn_info.amount.to_be_bytes())),
-                Token::Uint(Uint::from_big_endian(&nonce.to_be_bytes())),
-            ];
-            WITHDRAW_LIST.update(
+            assert!(
+                !is_expired(&chain_setting, &burn_info, &env),
+                "Withdraw expired"
+            );
+            assert_compliant(
                 deps.storage,
-                nonce,
-                |burn_info| -> Result<_, ContractError> {
-                    let mut burn_info = burn_info.unwrap();
-                    burn_info.timestamp = env.block.time;
-                    Ok(burn_info)
+                &chain_setting,
+                &burn_info.burner,
+                &burn_info.recipient,
+            );
+            if attempts_exhausted(&chain_setting, &burn_info) {
+                return Ok(response(mark_needs_review(deps.storage, nonce, burn_info)?));
+            }
+            let (job, event) = dispatch_retry(deps, &env, nonce, burn_info, &chain_setting)?;
+            Ok(response(event).add_message(job))
+        }
+        ExecuteMsg::RetryBatch {
+            chain_id,
+            start_after,
+            limit,
+        } => {
+            let state = STATE.load(deps.storage)?;
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            let (eligible, next_start_after) = scan_withdrawals(
+                deps.storage,
+                &chain_id,
+                start_after,
+                limit,
+                |storage, burn_info| {
+                    let due = burn_info.transport == Transport::Compass
+                        && burn_info.status == WithdrawStatus::Pending
+                        && burn_info.timestamp.plus_seconds(retry_delay(
+                            &state,
+                            &chain_setting,
+                            burn_info.attempts,
+                        )) < env.block.time
+                        && !is_expired(&chain_setting, burn_info, &env)
+                        && is_compliant(
+                            storage,
+                            &chain_setting,
+                            &burn_info.burner,
+                            &burn_info.recipient,
+                        );
+                    if due {
+                        Scan::Take
+                    } else {
+                        Scan::Skip
+                    }
                 },
             )?;
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                    execute_job: ExecuteJob {
-                        job_id: CHAIN_SETTINGS
-                            .load(deps.storage, burn_info.chain_id.clone())?
-                            .job_id,
-                        payload: Binary::new(
-                            contract
-                                .function("withdraw")
-                                .unwrap()
-                                .encode_input(tokens.as_slice())
-                                .unwrap(),
-                        ),
-                    },
-                }))
-                .add_attributes(vec![
-                    ("action", "re_withdraw"),
-                    ("chain_id", &burn_info.chain_id),
-                    ("recipient", burn_info.recipient.as_str()),
-                    ("nonce", &nonce.to_string()),
-                ]))
-        }
-        ExecuteMsg::UnmintPusd { amount } => {
+
+            let mut nonces = vec![];
+            let mut jobs = vec![];
+            let mut events = vec![];
+            for (nonce, burn_info) in eligible {
+                if attempts_exhausted(&chain_setting, &burn_info) {
+                    events.push(mark_needs_review(deps.storage, nonce, burn_info)?);
+                    continue;
+                }
+                let (job, event) =
+                    dispatch_retry(deps.branch(), &env, nonce, burn_info, &chain_setting)?;
+                nonces.push(nonce);
+                jobs.push(job);
+                events.push(event);
+            }
+
+            // Keepers are paid per dispatched job, as far as the pool allows
+            let mut reward = Uint128::zero();
+            if let Some(keeper_reward) = chain_setting.keeper_reward {
+                update_accounting(deps.storage, |accounting| {
+                    reward = keeper_reward
+                        .checked_mul(Uint128::from(nonces.len() as u128))?
+                        .min(accounting.keeper_pool);
+                    accounting.keeper_pool -= reward;
+                    Ok(())
+                })?;
+            }
+            let mut response = response(PusdEvent::RetryBatch {
+                chain_id,
+                nonces,
+                keeper: info.sender.clone(),
+                reward,
+                denom: state.denom.clone(),
+                next_start_after,
+            })
+            .add_messages(jobs)
+            .add_events(events);
+            if !reward.is_zero() {
+                response = response.add_message(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: vec![Coin {
+                        denom: state.denom,
+                        amount: reward,
+                    }],
+                }));
+            }
+            Ok(response)
+        }
+        ExecuteMsg::FundKeeperPool {} => {
+            let denom = STATE.load(deps.storage)?.denom;
+            let amount = info
+                .funds
+                .iter()
+                .filter(|coin| coin.denom == denom)
+                .map(|coin| coin.amount)
+                .sum::<Uint128>();
+            assert!(!amount.is_zero(), "Amount must be greater than 0");
+            let mut keeper_pool = Uint128::zero();
+            update_accounting(deps.storage, |accounting| {
+                accounting.keeper_pool += amount;
+                keeper_pool = accounting.keeper_pool;
+                Ok(())
+            })?;
+            Ok(response(PusdEvent::FundKeeperPool {
+                sender: info.sender,
+                amount,
+                keeper_pool,
+            }))
+        }
+        ExecuteMsg::ResetRetries { nonce } => {
+            assert!(
+                info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            let mut burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
+            burn_info.attempts = 0;
+            burn_info.status = WithdrawStatus::Pending;
+            WITHDRAW_LIST.save(deps.storage, nonce, &burn_info)?;
+            Ok(response(PusdEvent::ResetRetries {
+                nonce,
+                chain_id: burn_info.chain_id,
+            }))
+        }
+        ExecuteMsg::ImportEntries { entries } => {
+            assert!(
+                info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            assert!(
+                IMPORT_PROGRESS.exists(deps.storage),
+                "No import in progress"
+            );
+            let count = import_entries(deps.storage, entries)?;
+            Ok(response(PusdEvent::ImportEntries { count }))
+        }
+        ExecuteMsg::FinalizeImport {} => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            let Some(progress) = IMPORT_PROGRESS.may_load(deps.storage)? else {
+                panic!("No import in progress");
+            };
+            // Every entry of the export has to be imported, and the escrow funded
+            assert!(
+                progress.imported_entries == progress.expected_entries,
+                "Import incomplete"
+            );
+            let accounting = ACCOUNTING.load(deps.storage)?;
+            let mut withdrawal_count = 0;
+            let mut total_escrowed = Uint128::zero();
+            for item in WITHDRAW_LIST.range(deps.storage, None, None, Order::Ascending) {
+                let (_, burn_info) = item?;
+                withdrawal_count += 1;
+                total_escrowed += Uint128::from(burn_info.amount);
+            }
+            assert!(
+                total_escrowed == accounting.total_escrowed,
+                "Imported withdrawals don't match escrow"
+            );
+            let balance = deps
+                .querier
+                .query_balance(env.contract.address, state.denom)?
+                .amount;
+            assert!(
+                balance >= accounting.total_escrowed + accounting.keeper_pool,
+                "Insufficient balance for escrow"
+            );
+            IMPORT_PROGRESS.remove(deps.storage);
+            Ok(response(PusdEvent::FinalizeImport {
+                entry_count: progress.imported_entries,
+                withdrawal_count,
+                total_escrowed,
+            }))
+        }
+        ExecuteMsg::UnmintPusd {
+            amount,
+            reference,
+            burn,
+        } => {
             // ACTION: Implement UnmintPusd
             assert!(
                 info.sender == STATE.load(deps.storage)?.minter,
                 "Unauthorized"
             );
             assert!(!amount.is_zero(), "Amount must be greater than 0");
-            Ok(Response::new()
-                .add_message(CosmosMsg::Bank(BankMsg::Send {
+            let denom = STATE.load(deps.storage)?.denom;
+            assert_inventory(deps.as_ref(), &env, &denom, amount)?;
+            let burned = burn.unwrap_or(false);
+            update_accounting(deps.storage, |accounting| {
+                accounting.total_unminted += amount;
+                Ok(())
+            })?;
+            let id = LAST_REDEMPTION_ID
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            LAST_REDEMPTION_ID.save(deps.storage, &id)?;
+            if let Some(reference) = &reference {
+                assert!(!reference.is_empty(), "Reference cannot be empty");
+                assert!(
+                    !REDEMPTION_REFERENCES.has(deps.storage, reference.clone()),
+                    "Reference already used"
+                );
+                REDEMPTION_REFERENCES.save(deps.storage, reference.clone(), &id)?;
+            }
+            REDEMPTIONS.save(
+                deps.storage,
+                id,
+                &Redemption {
+                    minter: info.sender.clone(),
+                    amount,
+                    reference: reference.clone(),
+                    burned,
+                    timestamp: env.block.time,
+                },
+            )?;
+            REDEMPTION_TOTALS.update(
+                deps.storage,
+                info.sender.to_string(),
+                |totals| -> StdResult<_> {
+                    let mut totals = totals.unwrap_or_default();
+                    if burned {
+                        totals.total_burned += amount;
+                    } else {
+                        totals.total_transferred += amount;
+                    }
+                    totals.count += 1;
+                    Ok(totals)
+                },
+            )?;
+
+            let coins = vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }];
+            Ok(response(PusdEvent::Unmint {
+                redemption_id: id,
+                minter: info.sender.clone(),
+                amount,
+                denom,
+                burned,
+                reference,
+            })
+            .add_message(if burned {
+                CosmosMsg::Bank(BankMsg::Burn { amount: coins })
+            } else {
+                CosmosMsg::Bank(BankMsg::Send {
                     to_address: info.sender.to_string(),
-                    amount: vec![Coin {
-                        denom: STATE.load(deps.storage)?.denom,
-                        amount,
-                    }],
-                }))
-                .add_attributes(vec![
-                    ("action", "unmint_pusd"),
-                    ("minter", info.sender.as_str()),
-                    ("amount", &amount.to_string()),
-                ]))
+                    amount: coins,
+                })
+            }))
         }
         ExecuteMsg::CancelWithdraw { nonce } => {
             // ACTION: Implement CancelWithdraw
             let burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
-            assert!(burn_info.burner == info.sender, "Unauthorized");
+            assert!(
+                burn_info.burner == info.sender
+                    || burn_info.operator.as_ref() == Some(&info.sender),
+                "Unauthorized"
+            );
             // assert!(!burn_info.burned, "Already burned");
+            let chain_setting =
+                CHAIN_SETTINGS.may_load(deps.storage, burn_info.chain_id.clone())?;
             assert!(
-                burn_info
-                    .timestamp
-                    .plus_seconds(STATE.load(deps.storage)?.retry_delay)
-                    < env.block.time,
+                !in_flight(
+                    &STATE.load(deps.storage)?,
+                    chain_setting.as_ref(),
+                    &burn_info,
+                    &env
+                ),
                 "Withdraw is pending"
             );
-            WITHDRAW_LIST.remove(deps.storage, nonce);
-            Ok(Response::new()
-                .add_message(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: info.sender.to_string(),
-                    amount: vec![Coin {
-                        denom: STATE.load(deps.storage)?.denom,
-                        amount: Uint128::from(burn_info.amount),
-                    }],
-                }))
-                .add_attributes(vec![
-                    ("action", "cancel_withdraw"),
-                    ("nonce", &nonce.to_string()),
-                    ("chain_id", &burn_info.chain_id),
-                    ("recipient", burn_info.recipient.as_str()),
-                    ("burner", burn_info.burner.as_str()),
-                    ("amount", &burn_info.amount.to_string()),
-                ]))
-        }
-        ExecuteMsg::UpdateConfig { retry_delay, owner } => {
-            // ACTION: Implement UpdateConfig
+            let (refund_address, refund) = refund_withdraw(deps.storage, nonce, &burn_info)?;
+            Ok(response(PusdEvent::CancelWithdraw {
+                nonce,
+                chain_id: burn_info.chain_id,
+                burner: burn_info.burner,
+                recipient: burn_info.recipient,
+                amount: Uint128::from(burn_info.amount),
+                denom: STATE.load(deps.storage)?.denom,
+                refund_address,
+            })
+            .add_messages(refund))
+        }
+        ExecuteMsg::RefundExpired {
+            chain_id,
+            start_after,
+            limit,
+        } => {
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            let (expired, next_start_after) = expired_withdrawals(
+                deps.as_ref(),
+                &env,
+                &chain_id,
+                &chain_setting,
+                start_after,
+                limit,
+            )?;
+            let mut nonces = vec![];
+            let mut amount = Uint128::zero();
+            let mut refunds = vec![];
+            for (nonce, burn_info) in expired {
+                let (_, refund) = refund_withdraw(deps.storage, nonce, &burn_info)?;
+                amount += Uint128::from(burn_info.amount);
+                EXPIRED_WITHDRAWALS.save(
+                    deps.storage,
+                    nonce,
+                    &ExpiredWithdrawal {
+                        burn_info,
+                        refunded_at: env.block.time,
+                    },
+                )?;
+                nonces.push(nonce);
+                refunds.extend(refund);
+            }
+            Ok(response(PusdEvent::RefundExpired {
+                chain_id,
+                nonces,
+                amount,
+                denom: STATE.load(deps.storage)?.denom,
+                sender: info.sender,
+                next_start_after,
+            })
+            .add_messages(refunds))
+        }
+        ExecuteMsg::ForceRefund { nonce } => {
+            let burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
+            let state = STATE.load(deps.storage)?;
             assert!(
-                info.sender == STATE.load(deps.storage)?.owner,
+                info.sender == state.owner || burn_info.operator.as_ref() == Some(&info.sender),
                 "Unauthorized"
             );
+            // A withdraw still in flight may yet be executed on the remote chain
+            let chain_setting =
+                CHAIN_SETTINGS.may_load(deps.storage, burn_info.chain_id.clone())?;
+            assert!(
+                !in_flight(&state, chain_setting.as_ref(), &burn_info, &env),
+                "Withdraw is pending"
+            );
+            let (refund_address, refund) = refund_withdraw(deps.storage, nonce, &burn_info)?;
+            Ok(response(PusdEvent::ForceRefund {
+                nonce,
+                chain_id: burn_info.chain_id,
+                burner: burn_info.burner,
+                amount: Uint128::from(burn_info.amount),
+                denom: state.denom,
+                refund_address,
+                sender: info.sender,
+            })
+            .add_messages(refund))
+        }
+        ExecuteMsg::UpdateConfig {
+            retry_delay,
+            owner,
+            timelock_delay,
+        } => {
+            // ACTION: Implement UpdateConfig
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            // Ownership changes and lowering the delay have to go through the timelock queue
+            assert!(
+                owner.is_none() || state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            assert!(
+                timelock_delay.unwrap_or(state.timelock_delay) >= state.timelock_delay,
+                "Timelocked, use ProposeAdminAction"
+            );
             STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
                 if let Some(retry_delay) = retry_delay {
                     assert!(retry_delay > 0, "Retry delay must be greater than 0");
@@ -425,22 +1002,93 @@ pub fn execute(
                 if let Some(new_owner) = owner.clone() {
                     state.owner = new_owner;
                 }
+                if let Some(timelock_delay) = timelock_delay {
+                    state.timelock_delay = timelock_delay;
+                }
                 Ok(state)
             })?;
-            let mut attributes = vec![("action", "update_config")];
-            let retry_delay_string: String;
-            if retry_delay.is_some() {
-                retry_delay_string = retry_delay.unwrap().to_string();
-                attributes.push(("retry_delay", retry_delay_string.as_str()));
+            Ok(response(PusdEvent::UpdateConfig {
+                retry_delay,
+                owner,
+                timelock_delay,
+            }))
+        }
+        ExecuteMsg::ProposeAdminAction { action } => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            // Refuse an invalid action now rather than after the timelock
+            assert_admin_action(&action);
+            let id = LAST_ADMIN_ACTION_ID.may_load(deps.storage)?.unwrap_or(0) + 1;
+            let eta = env.block.time.plus_seconds(state.timelock_delay);
+            let admin_action = action.name().to_string();
+            QUEUED_ADMIN_ACTIONS.save(
+                deps.storage,
+                id,
+                &QueuedAdminAction {
+                    action,
+                    proposer: info.sender.clone(),
+                    queued_at: env.block.time,
+                    eta,
+                },
+            )?;
+            LAST_ADMIN_ACTION_ID.save(deps.storage, &id)?;
+            Ok(response(PusdEvent::ProposeAdminAction {
+                id,
+                admin_action,
+                eta: eta.seconds(),
+            }))
+        }
+        ExecuteMsg::ExecuteAdminAction { id } => {
+            assert!(
+                info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            let queued_action = QUEUED_ADMIN_ACTIONS.load(deps.storage, id)?;
+            assert!(queued_action.eta <= env.block.time, "Timelock not expired");
+            QUEUED_ADMIN_ACTIONS.remove(deps.storage, id);
+            let admin_action = queued_action.action.name().to_string();
+            Ok(
+                execute_admin_action(deps, env, &info.sender, queued_action.action)?
+                    .add_event(PusdEvent::ExecuteAdminAction { id, admin_action }),
+            )
+        }
+        ExecuteMsg::CancelAdminAction { id } => {
+            assert!(
+                info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            assert!(
+                QUEUED_ADMIN_ACTIONS.has(deps.storage, id),
+                "Admin action not queued"
+            );
+            QUEUED_ADMIN_ACTIONS.remove(deps.storage, id);
+            Ok(response(PusdEvent::CancelAdminAction { id }))
+        }
+
+        ExecuteMsg::UpdateComplianceList { list, add, remove } => {
+            assert!(
+                info.sender == STATE.load(deps.storage)?.owner,
+                "Unauthorized"
+            );
+            let members = list.members();
+            let mut added = vec![];
+            for member in add {
+                let member = compliance_member(deps.as_ref(), &list, &member)?;
+                members.save(deps.storage, member.clone(), &env.block.time)?;
+                added.push(member);
             }
-            let owner_string: String;
-            if owner.is_some() {
-                owner_string = owner.unwrap().to_string();
-                attributes.push(("owner", owner_string.as_str()));
+            let mut removed = vec![];
+            for member in remove {
+                let member = compliance_member(deps.as_ref(), &list, &member)?;
+                members.remove(deps.storage, member.clone());
+                removed.push(member);
             }
-            Ok(Response::new().add_attributes(attributes))
+            Ok(response(PusdEvent::UpdateComplianceList {
+                list: list.name().to_string(),
+                added,
+                removed,
+            }))
         }
-
         ExecuteMsg::SetPaloma { chain_id } => {
             // ACTION: Implement SetPaloma
             let state = STATE.load(deps.storage)?;
@@ -464,20 +1112,31 @@ pub fn execute(
                 receive: false,
                 fallback: false,
             };
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                    execute_job: ExecuteJob {
-                        job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                        payload: Binary::new(
-                            contract
-                                .function("set_paloma")
-                                .unwrap()
-                                .encode_input(&[])
-                                .unwrap(),
-                        ),
-                    },
-                }))
-                .add_attribute("action", "set_paloma"))
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            REMOTE_CONFIGS.update(
+                deps.storage,
+                chain_id.clone(),
+                |remote_config| -> StdResult<_> {
+                    let mut remote_config = remote_config.unwrap_or_default();
+                    remote_config.paloma_set = true;
+                    remote_config.updated_at = Some(env.block.time);
+                    Ok(remote_config)
+                },
+            )?;
+            Ok(
+                response(PusdEvent::SetPaloma { chain_id }).add_message(scheduler_job(
+                    deps.as_ref(),
+                    &chain_setting,
+                    JobKind::Admin,
+                    Binary::new(
+                        contract
+                            .function("set_paloma")
+                            .unwrap()
+                            .encode_input(&[])
+                            .unwrap(),
+                    ),
+                )),
+            )
         }
         ExecuteMsg::UpdateCompass {
             chain_id,
@@ -485,7 +1144,200 @@ pub fn execute(
         } => {
             let state = STATE.load(deps.storage)?;
             assert!(info.sender == state.owner, "Unauthorized");
+            assert!(
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::UpdateCompass {
+                    chain_id,
+                    new_compass,
+                },
+            )
+        }
+        ExecuteMsg::UpdateRefundWallet {
+            chain_id,
+            new_refund_wallet,
+        } => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            assert!(
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::UpdateRefundWallet {
+                    chain_id,
+                    new_refund_wallet,
+                },
+            )
+        }
+        ExecuteMsg::UpdateRedemptionFee {
+            chain_id,
+            new_redemption_fee,
+        } => {
+            let state = STATE.load(deps.storage)?;
+            assert!(info.sender == state.owner, "Unauthorized");
+            assert!(
+                state.timelock_delay == 0,
+                "Timelocked, use ProposeAdminAction"
+            );
+            execute_admin_action(
+                deps,
+                env,
+                &info.sender,
+                AdminAction::UpdateRedemptionFee {
+                    chain_id,
+                    new_redemption_fee,
+                },
+            )
+        }
+    }
+}
 
+// Checks of an admin action that don't depend on the state, run both when it is
+// proposed and when it is applied
+fn assert_admin_action(action: &AdminAction) {
+    match action {
+        AdminAction::RegisterChain {
+            chain_id,
+            chain_setting,
+        } => {
+            assert!(!chain_id.is_empty(), "Chain ID cannot be empty");
+            assert_chain_setting(chain_setting);
+        }
+        AdminAction::UpdateChain { chain_setting, .. } => assert_chain_setting(chain_setting),
+        AdminAction::UpdateCompass { new_compass, .. } => assert!(
+            Address::from_str(new_compass).is_ok(),
+            "Invalid compass address"
+        ),
+        AdminAction::UpdateRefundWallet {
+            new_refund_wallet, ..
+        } => assert!(
+            Address::from_str(new_refund_wallet).is_ok(),
+            "Invalid refund wallet address"
+        ),
+        AdminAction::UpdateMintApprovalConfig {
+            config: Some(config),
+        } => {
+            assert!(config.threshold > 0, "Threshold must be greater than 0");
+            assert!(
+                config.threshold as usize <= config.approvers.len(),
+                "Threshold exceeds number of approvers"
+            );
+        }
+        _ => {}
+    }
+}
+
+// Apply a sensitive admin action, either directly while no timelock is set or from the queue
+fn execute_admin_action(
+    deps: DepsMut<PalomaQuery>,
+    env: Env,
+    sender: &Addr,
+    action: AdminAction,
+) -> Result<Response<PalomaMsg>, ContractError> {
+    assert_admin_action(&action);
+    match action {
+        AdminAction::RegisterChain {
+            chain_id,
+            chain_setting,
+        } => {
+            assert_jobs_exist(deps.as_ref(), &chain_setting);
+            assert!(
+                !CHAIN_SETTINGS.has(deps.storage, chain_id.clone()),
+                "Chain already registered"
+            );
+            CHAIN_SETTINGS.save(deps.storage, chain_id.clone(), &chain_setting)?;
+            record_chain_setting_change(
+                deps.storage,
+                &env,
+                sender,
+                &chain_id,
+                ChainSettingAction::Register,
+                Some(chain_setting.clone()),
+            )?;
+            Ok(response(PusdEvent::RegisterChain {
+                chain_id,
+                job_id: chain_setting.job_id,
+                admin_job_id: chain_setting.admin_job_id,
+                minimum_amount: chain_setting.minimum_amount,
+                decimals: chain_setting.decimals,
+            }))
+        }
+        AdminAction::UpdateChain {
+            chain_id,
+            chain_setting,
+        } => {
+            assert_jobs_exist(deps.as_ref(), &chain_setting);
+            assert!(
+                CHAIN_SETTINGS.has(deps.storage, chain_id.clone()),
+                "Chain not registered"
+            );
+            CHAIN_SETTINGS.save(deps.storage, chain_id.clone(), &chain_setting)?;
+            record_chain_setting_change(
+                deps.storage,
+                &env,
+                sender,
+                &chain_id,
+                ChainSettingAction::Update,
+                Some(chain_setting.clone()),
+            )?;
+            Ok(response(PusdEvent::UpdateChain {
+                chain_id,
+                job_id: chain_setting.job_id,
+                admin_job_id: chain_setting.admin_job_id,
+                minimum_amount: chain_setting.minimum_amount,
+                decimals: chain_setting.decimals,
+            }))
+        }
+        AdminAction::DeregisterChain { chain_id, force } => {
+            assert!(
+                CHAIN_SETTINGS.has(deps.storage, chain_id.clone()),
+                "Chain not registered"
+            );
+            let pending = CHAIN_WITHDRAWALS.prefix(chain_id.clone());
+            let force = force.unwrap_or(false);
+            assert!(
+                force
+                    || pending
+                        .keys(deps.storage, None, None, Order::Ascending)
+                        .next()
+                        .is_none(),
+                "Pending withdrawals exist for chain"
+            );
+            let pending_count = if force {
+                pending
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .count() as u64
+            } else {
+                0
+            };
+            CHAIN_SETTINGS.remove(deps.storage, chain_id.clone());
+            BRIDGES.remove(deps.storage, chain_id.clone());
+            record_chain_setting_change(
+                deps.storage,
+                &env,
+                sender,
+                &chain_id,
+                ChainSettingAction::Deregister,
+                None,
+            )?;
+            Ok(response(PusdEvent::DeregisterChain {
+                chain_id,
+                pending_count,
+            }))
+        }
+        AdminAction::UpdateCompass {
+            chain_id,
+            new_compass,
+        } => {
             #[allow(deprecated)]
             let contract: Contract = Contract {
                 constructor: None,
@@ -509,35 +1361,43 @@ pub fn execute(
                 fallback: false,
             };
             let tokens = &[Token::Address(
-                Address::from_str(new_compass.as_str()).unwrap(),
+                Address::from_str(new_compass.as_str()).expect("Invalid compass address"),
             )];
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                    execute_job: ExecuteJob {
-                        job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                        payload: Binary::new(
-                            contract
-                                .function("update_compass")
-                                .unwrap()
-                                .encode_input(tokens)
-                                .unwrap(),
-                        ),
-                    },
-                }))
-                .add_attributes(vec![
-                    ("action", "update_compass"),
-                    ("chain_id", &chain_id),
-                    ("new_compass", new_compass.as_str()),
-                ]))
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            REMOTE_CONFIGS.update(
+                deps.storage,
+                chain_id.clone(),
+                |remote_config| -> StdResult<_> {
+                    let mut remote_config = remote_config.unwrap_or_default();
+                    remote_config.compass = Some(new_compass.clone());
+                    remote_config.updated_at = Some(env.block.time);
+                    Ok(remote_config)
+                },
+            )?;
+            Ok(response(PusdEvent::UpdateCompass {
+                chain_id,
+                new_compass,
+            })
+            .add_message(scheduler_job(
+                deps.as_ref(),
+                &chain_setting,
+                JobKind::Admin,
+                Binary::new(
+                    contract
+                        .function("update_compass")
+                        .unwrap()
+                        .encode_input(tokens)
+                        .unwrap(),
+                ),
+            )))
         }
-        ExecuteMsg::UpdateRefundWallet {
+        AdminAction::UpdateRefundWallet {
             chain_id,
             new_refund_wallet,
         } => {
-            let state = STATE.load(deps.storage)?;
-            assert!(state.owner == info.sender, "Unauthorized");
             let update_refund_wallet_address: Address =
-                Address::from_str(new_refund_wallet.as_str()).unwrap();
+                Address::from_str(new_refund_wallet.as_str())
+                    .expect("Invalid refund wallet address");
             #[allow(deprecated)]
             let contract: Contract = Contract {
                 constructor: None,
@@ -560,28 +1420,38 @@ pub fn execute(
                 receive: false,
                 fallback: false,
             };
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                    execute_job: ExecuteJob {
-                        job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                        payload: Binary::new(
-                            contract
-                                .function("update_refund_wallet")
-                                .unwrap()
-                                .encode_input(&[Token::Address(update_refund_wallet_address)])
-                                .unwrap(),
-                        ),
-                    },
-                }))
-                .add_attribute("action", "update_refund_wallet"))
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            REMOTE_CONFIGS.update(
+                deps.storage,
+                chain_id.clone(),
+                |remote_config| -> StdResult<_> {
+                    let mut remote_config = remote_config.unwrap_or_default();
+                    remote_config.refund_wallet = Some(new_refund_wallet.clone());
+                    remote_config.updated_at = Some(env.block.time);
+                    Ok(remote_config)
+                },
+            )?;
+            Ok(response(PusdEvent::UpdateRefundWallet {
+                chain_id,
+                new_refund_wallet,
+            })
+            .add_message(scheduler_job(
+                deps.as_ref(),
+                &chain_setting,
+                JobKind::Admin,
+                Binary::new(
+                    contract
+                        .function("update_refund_wallet")
+                        .unwrap()
+                        .encode_input(&[Token::Address(update_refund_wallet_address)])
+                        .unwrap(),
+                ),
+            )))
         }
-        ExecuteMsg::UpdateRedemptionFee {
+        AdminAction::UpdateRedemptionFee {
             chain_id,
             new_redemption_fee,
         } => {
-            let state = STATE.load(deps.storage)?;
-            assert!(state.owner == info.sender, "Unauthorized");
-
             let redemption_fee = new_redemption_fee.u128();
             let redemption_fee_bytes = Uint::from_big_endian(&redemption_fee.to_be_bytes());
             #[allow(deprecated)]
@@ -606,26 +1476,95 @@ pub fn execute(
                 receive: false,
                 fallback: false,
             };
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg::SchedulerMsg {
-                    execute_job: ExecuteJob {
-                        job_id: CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?.job_id,
-                        payload: Binary::new(
-                            contract
-                                .function("update_redemption_fee")
-                                .unwrap()
-                                .encode_input(&[Token::Uint(redemption_fee_bytes)])
-                                .unwrap(),
-                        ),
-                    },
-                }))
-                .add_attribute("action", "update_redemption_fee"))
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            REMOTE_CONFIGS.update(
+                deps.storage,
+                chain_id.clone(),
+                |remote_config| -> StdResult<_> {
+                    let mut remote_config = remote_config.unwrap_or_default();
+                    remote_config.redemption_fee = Some(new_redemption_fee);
+                    remote_config.updated_at = Some(env.block.time);
+                    Ok(remote_config)
+                },
+            )?;
+            Ok(response(PusdEvent::UpdateRedemptionFee {
+                chain_id,
+                new_redemption_fee,
+            })
+            .add_message(scheduler_job(
+                deps.as_ref(),
+                &chain_setting,
+                JobKind::Admin,
+                Binary::new(
+                    contract
+                        .function("update_redemption_fee")
+                        .unwrap()
+                        .encode_input(&[Token::Uint(redemption_fee_bytes)])
+                        .unwrap(),
+                ),
+            )))
+        }
+        AdminAction::UpdateOwner { owner } => {
+            STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
+                state.owner = owner.clone();
+                Ok(state)
+            })?;
+            Ok(response(PusdEvent::UpdateOwner { owner }))
+        }
+        AdminAction::UpdateMintApprovalConfig { config } => {
+            match &config {
+                Some(config) => MINT_APPROVAL_CONFIG.save(deps.storage, config)?,
+                None => MINT_APPROVAL_CONFIG.remove(deps.storage),
+            }
+            Ok(response(PusdEvent::UpdateMintApprovalConfig {
+                enabled: config.is_some(),
+                threshold: config.as_ref().map(|config| config.threshold),
+                minimum_amount: config.as_ref().map(|config| config.minimum_amount),
+            }))
+        }
+        AdminAction::UpdateTimelockDelay { timelock_delay } => {
+            STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
+                state.timelock_delay = timelock_delay;
+                Ok(state)
+            })?;
+            Ok(response(PusdEvent::UpdateTimelockDelay { timelock_delay }))
         }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(
+    deps: DepsMut<PalomaQuery>,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response<PalomaMsg>, ContractError> {
+    match msg.id {
+        SKYWAY_SEND_REPLY_ID => {
+            let nonce: u64 = from_json(&msg.payload)?;
+            let send_response = msg.result.into_result().map_err(StdError::generic_err)?;
+            // Skyway reports the id of the queued transfer in its events
+            let transaction_id = send_response
+                .events
+                .iter()
+                .flat_map(|event| event.attributes.iter())
+                .find(|attribute| attribute.key == "outgoing_tx_id")
+                .and_then(|attribute| attribute.value.parse::<u64>().ok())
+                .expect("Missing Skyway transaction id");
+            let mut burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
+            burn_info.skyway_tx_id = Some(transaction_id);
+            WITHDRAW_LIST.save(deps.storage, nonce, &burn_info)?;
+            Ok(response(PusdEvent::SkywaySend {
+                nonce,
+                chain_id: burn_info.chain_id,
+                transaction_id,
+            }))
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id {}", id)).into()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<PalomaQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetState {} => to_json_binary(&STATE.load(deps.storage)?),
         QueryMsg::GetWithdrawList {} => {
@@ -649,7 +1588,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     chain_setting_info.push(ChainSettingInfo {
                         chain_id: item.clone().0,
                         job_id: item.1.job_id.clone(),
+                        admin_job_id: item.1.admin_job_id.clone(),
                         minimum_amount: item.1.minimum_amount,
+                        decimals: item.1.decimals,
+                        allowlist_only: item.1.allowlist_only,
+                        transport: item.1.transport.clone(),
                     });
                 });
             to_json_binary(&chain_setting_info)
@@ -657,7 +1600,284 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetJobId { chain_id } => {
             to_json_binary(&CHAIN_SETTINGS.load(deps.storage, chain_id)?)
         }
+        QueryMsg::GetChainSettingHistory { chain_id } => {
+            let history = CHAIN_SETTING_HISTORY
+                .prefix(chain_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, change)| change))
+                .collect::<StdResult<Vec<ChainSettingChange>>>()?;
+            to_json_binary(&history)
+        }
+        QueryMsg::GetQueuedAdminActions {} => {
+            let queued_actions = QUEUED_ADMIN_ACTIONS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<(u64, QueuedAdminAction)>>>()?;
+            to_json_binary(&queued_actions)
+        }
+        QueryMsg::GetMintApprovalConfig {} => {
+            to_json_binary(&MINT_APPROVAL_CONFIG.may_load(deps.storage)?)
+        }
+        QueryMsg::GetMintRequest { id } => to_json_binary(&MINT_REQUESTS.load(deps.storage, id)?),
+        QueryMsg::GetOpenMintRequests {} => {
+            let mut open_requests: Vec<(u64, MintRequest)> = Vec::new();
+            for item in MINT_REQUESTS.range(deps.storage, None, None, Order::Ascending) {
+                let (id, mint_request) = item?;
+                if !mint_request.executed && env.block.time < mint_request.expires_at {
+                    open_requests.push((id, mint_request));
+                }
+            }
+            to_json_binary(&open_requests)
+        }
+        QueryMsg::GetRedemptions {
+            minter,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let minter = minter
+                .map(|minter| deps.api.addr_validate(&minter))
+                .transpose()?;
+            let mut redemptions = vec![];
+            for item in REDEMPTIONS.range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            ) {
+                let (id, redemption) = item?;
+                if minter
+                    .as_ref()
+                    .is_none_or(|minter| redemption.minter == *minter)
+                {
+                    redemptions.push((id, redemption));
+                    if redemptions.len() == limit {
+                        break;
+                    }
+                }
+            }
+            to_json_binary(&redemptions)
+        }
+        QueryMsg::GetRedemptionByReference { reference } => {
+            let redemption = REDEMPTION_REFERENCES
+                .may_load(deps.storage, reference)?
+                .map(|id| -> StdResult<_> { Ok((id, REDEMPTIONS.load(deps.storage, id)?)) })
+                .transpose()?;
+            to_json_binary(&redemption)
+        }
+        QueryMsg::GetRedemptionTotals { minter } => to_json_binary(
+            &REDEMPTION_TOTALS
+                .may_load(deps.storage, minter)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::GetRemoteConfig { chain_id } => to_json_binary(
+            &REMOTE_CONFIGS
+                .may_load(deps.storage, chain_id)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::GetBridge { chain_reference_id } => {
+            let bridge = BRIDGES.load(deps.storage, chain_reference_id.clone())?;
+            let history = BRIDGE_HISTORY
+                .prefix(chain_reference_id.clone())
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, bridge_info)| bridge_info))
+                .collect::<StdResult<Vec<BridgeInfo>>>()?;
+            to_json_binary(&BridgeResponse {
+                chain_reference_id,
+                bridge,
+                history,
+            })
+        }
+        QueryMsg::ComplianceStatus { sender, recipient } => {
+            let sender = sender.unwrap_or_default();
+            to_json_binary(&ComplianceStatusResponse {
+                sender_denylisted: SENDER_DENYLIST.has(deps.storage, sender.clone()),
+                sender_allowlisted: ALLOWLIST.has(deps.storage, sender),
+                recipient_denylisted: RECIPIENT_DENYLIST
+                    .has(deps.storage, recipient.unwrap_or_default().to_lowercase()),
+            })
+        }
+        QueryMsg::GetComplianceList { list } => {
+            let members = list
+                .members()
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<String>>>()?;
+            to_json_binary(&members)
+        }
+        QueryMsg::GetExpiredWithdrawals {
+            chain_id,
+            start_after,
+            limit,
+        } => {
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            let (expired, next_start_after) =
+                expired_withdrawals(deps, &env, &chain_id, &chain_setting, start_after, limit)?;
+            to_json_binary(&ExpiredWithdrawalsResponse {
+                nonces: expired.into_iter().map(|(nonce, _)| nonce).collect(),
+                next_start_after,
+            })
+        }
+        QueryMsg::GetExpiredWithdrawal { nonce } => {
+            to_json_binary(&EXPIRED_WITHDRAWALS.may_load(deps.storage, nonce)?)
+        }
+        QueryMsg::ExportState { limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+            let (entries, next_start_after) = export_entries(deps.storage, None, limit)?;
+            to_json_binary(&StateExport {
+                version: EXPORT_VERSION,
+                contract_version: get_contract_version(deps.storage)?.version,
+                state: STATE.load(deps.storage)?,
+                accounting: ACCOUNTING.may_load(deps.storage)?.unwrap_or_default(),
+                counters: ExportCounters {
+                    last_admin_action_id: LAST_ADMIN_ACTION_ID
+                        .may_load(deps.storage)?
+                        .unwrap_or_default(),
+                    last_mint_request_id: LAST_MINT_REQUEST_ID
+                        .may_load(deps.storage)?
+                        .unwrap_or_default(),
+                    last_redemption_id: LAST_REDEMPTION_ID
+                        .may_load(deps.storage)?
+                        .unwrap_or_default(),
+                },
+                chain_settings: CHAIN_SETTINGS
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?,
+                chain_accounting: CHAIN_ACCOUNTING
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?,
+                bridges: BRIDGES
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?,
+                remote_configs: REMOTE_CONFIGS
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?,
+                mint_approval_config: MINT_APPROVAL_CONFIG.may_load(deps.storage)?,
+                queued_admin_actions: QUEUED_ADMIN_ACTIONS
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?,
+                redemption_totals: REDEMPTION_TOTALS
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<_>>()?,
+                entry_count: export_entry_count(deps.storage)?,
+                entries,
+                next_start_after,
+            })
+        }
+        QueryMsg::ExportEntries { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+            let (entries, next_start_after) =
+                export_entries(deps.storage, Some(start_after), limit)?;
+            to_json_binary(&ExportPage {
+                entries,
+                next_start_after,
+            })
+        }
+        QueryMsg::Reconciliation { chain_id } => {
+            let chain_accounting = CHAIN_ACCOUNTING
+                .may_load(deps.storage, chain_id.clone())?
+                .unwrap_or_default();
+            let mut pending_amount = Uint128::zero();
+            let mut pending_count = 0;
+            let mut oldest_pending: Option<(u64, BurnInfo)> = None;
+            // Nonces ascend, so the first pending withdrawal is the oldest
+            for nonce in CHAIN_WITHDRAWALS.prefix(chain_id.clone()).keys(
+                deps.storage,
+                None,
+                None,
+                Order::Ascending,
+            ) {
+                let nonce = nonce?;
+                let burn_info = WITHDRAW_LIST.load(deps.storage, nonce)?;
+                pending_amount += Uint128::from(burn_info.amount);
+                pending_count += 1;
+                if oldest_pending.is_none() {
+                    oldest_pending = Some((nonce, burn_info));
+                }
+            }
+            to_json_binary(&ReconciliationResponse {
+                chain_id,
+                total_withdrawn: chain_accounting.total_withdrawn,
+                total_burned: chain_accounting.total_burned,
+                total_cancelled: chain_accounting.total_cancelled,
+                pending_amount,
+                pending_count,
+                oldest_pending_nonce: oldest_pending.as_ref().map(|(nonce, _)| *nonce),
+                oldest_pending_age: oldest_pending.map(|(_, burn_info)| {
+                    // Imported withdrawals can carry a created_at after the current block time
+                    env.block
+                        .time
+                        .seconds()
+                        .saturating_sub(burn_info.created_at().seconds())
+                }),
+                balanced: chain_accounting.total_withdrawn
+                    == chain_accounting.total_burned
+                        + chain_accounting.total_cancelled
+                        + pending_amount,
+            })
+        }
+        QueryMsg::UserQuota { address, chain_id } => {
+            let address = deps.api.addr_validate(&address)?;
+            let chain_setting = CHAIN_SETTINGS.load(deps.storage, chain_id.clone())?;
+            let (usage, oldest_bucket) =
+                quota_usage(deps.storage, &env, address.as_str(), &chain_id)?;
+            let exempt = ALLOWLIST.has(deps.storage, address.to_string());
+            let quota = chain_setting.quota.filter(|_| !exempt);
+            to_json_binary(&UserQuotaResponse {
+                exempt,
+                used_amount: usage.amount,
+                used_count: usage.count,
+                remaining_amount: quota
+                    .as_ref()
+                    .and_then(|quota| quota.max_amount)
+                    .map(|max_amount| max_amount.saturating_sub(usage.amount)),
+                remaining_count: quota
+                    .as_ref()
+                    .and_then(|quota| quota.max_count)
+                    .map(|max_count| max_count.saturating_sub(usage.count)),
+                resets_at: oldest_bucket.map_or(env.block.time, |bucket| {
+                    Timestamp::from_seconds((bucket + QUOTA_BUCKETS) * QUOTA_BUCKET)
+                }),
+            })
+        }
+        QueryMsg::GetAllowance {
+            owner,
+            spender,
+            chain_id,
+        } => to_json_binary(
+            &ALLOWANCES
+                .may_load(deps.storage, (owner, spender, chain_id))?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::ListBridges {} => {
+            let bridges = BRIDGES
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<(String, BridgeInfo)>>>()?;
+            to_json_binary(&bridges)
+        }
         QueryMsg::ReWithdrawable {} => to_json_binary(&!WITHDRAW_LIST.is_empty(deps.storage)),
+        QueryMsg::Solvency {} => {
+            let accounting = ACCOUNTING.may_load(deps.storage)?.unwrap_or_default();
+            let balance = deps
+                .querier
+                .query_balance(env.contract.address, STATE.load(deps.storage)?.denom)?
+                .amount;
+            let reserved = accounting.total_escrowed + accounting.keeper_pool;
+            to_json_binary(&SolvencyResponse {
+                balance,
+                total_escrowed: accounting.total_escrowed,
+                keeper_pool: accounting.keeper_pool,
+                total_in_transit: accounting.total_in_transit,
+                mint_inventory: balance.saturating_sub(reserved),
+                total_minted: accounting.total_minted,
+                total_burned: accounting.total_burned,
+                total_unminted: accounting.total_unminted,
+                solvent: balance >= reserved,
+            })
+        }
+        QueryMsg::GetChainAccounting { chain_id } => to_json_binary(
+            &CHAIN_ACCOUNTING
+                .may_load(deps.storage, chain_id)?
+                .unwrap_or_default(),
+        ),
         QueryMsg::PusdBalance {} => to_json_binary(&BalanceResponse {
             balance: deps
                 .querier
@@ -669,3 +1889,919 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }),
     }
 }
+
+// Restore the state of an export and its first page of withdrawals. The contract refuses
+// everything but further imports until FinalizeImport.
+fn import_state(
+    deps: DepsMut<PalomaQuery>,
+    info: &MessageInfo,
+    export: StateExport,
+) -> Result<Response<PalomaMsg>, ContractError> {
+    assert!(
+        export.version == EXPORT_VERSION,
+        "Unsupported export version"
+    );
+    assert!(info.sender == export.state.owner, "Unauthorized");
+    // A Skyway transfer can only be cancelled by the contract that sent it
+    assert!(
+        export.accounting.total_in_transit.is_zero(),
+        "Skyway withdrawals must be settled before export"
+    );
+    STATE.save(deps.storage, &export.state)?;
+    ACCOUNTING.save(deps.storage, &export.accounting)?;
+    LAST_ADMIN_ACTION_ID.save(deps.storage, &export.counters.last_admin_action_id)?;
+    LAST_MINT_REQUEST_ID.save(deps.storage, &export.counters.last_mint_request_id)?;
+    LAST_REDEMPTION_ID.save(deps.storage, &export.counters.last_redemption_id)?;
+    for (chain_id, chain_setting) in &export.chain_settings {
+        CHAIN_SETTINGS.save(deps.storage, chain_id.clone(), chain_setting)?;
+    }
+    for (chain_id, chain_accounting) in &export.chain_accounting {
+        CHAIN_ACCOUNTING.save(deps.storage, chain_id.clone(), chain_accounting)?;
+    }
+    for (chain_reference_id, bridge_info) in &export.bridges {
+        BRIDGES.save(deps.storage, chain_reference_id.clone(), bridge_info)?;
+    }
+    for (chain_id, remote_config) in &export.remote_configs {
+        REMOTE_CONFIGS.save(deps.storage, chain_id.clone(), remote_config)?;
+    }
+    if let Some(mint_approval_config) = &export.mint_approval_config {
+        MINT_APPROVAL_CONFIG.save(deps.storage, mint_approval_config)?;
+    }
+    for (id, queued_admin_action) in &export.queued_admin_actions {
+        QUEUED_ADMIN_ACTIONS.save(deps.storage, *id, queued_admin_action)?;
+    }
+    for (minter, redemption_totals) in &export.redemption_totals {
+        REDEMPTION_TOTALS.save(deps.storage, minter.clone(), redemption_totals)?;
+    }
+    IMPORT_PROGRESS.save(
+        deps.storage,
+        &ImportProgress {
+            expected_entries: export.entry_count,
+            imported_entries: 0,
+        },
+    )?;
+    let entry_count = import_entries(deps.storage, export.entries)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(response(PusdEvent::ImportState {
+        version: export.version,
+        owner: export.state.owner,
+        chain_count: export.chain_settings.len() as u64,
+        entry_count,
+        expected_entries: export.entry_count,
+    }))
+}
+
+// Restore the entries of an export page, each at most once, and count them towards the
+// entry count of the export
+fn import_entries(storage: &mut dyn Storage, entries: Vec<ExportEntry>) -> StdResult<u64> {
+    let state = STATE.load(storage)?;
+    let count = entries.len() as u64;
+    for entry in entries {
+        match entry {
+            ExportEntry::Withdrawal { nonce, burn_info } => {
+                // Withdrawals of a forcibly deregistered chain stay pending
+                assert!(nonce > 0 && nonce <= state.last_nonce, "Nonce out of range");
+                assert!(
+                    burn_info.transport == Transport::Compass,
+                    "Skyway withdrawals must be settled before export"
+                );
+                assert!(!WITHDRAW_LIST.has(storage, nonce), "Entry already imported");
+                CHAIN_WITHDRAWALS.save(storage, (burn_info.chain_id.clone(), nonce), &Empty {})?;
+                WITHDRAW_LIST.save(storage, nonce, &burn_info)?;
+            }
+            ExportEntry::ComplianceMember {
+                list,
+                member,
+                added_at,
+            } => {
+                let members = list.members();
+                assert!(
+                    !members.has(storage, member.clone()),
+                    "Entry already imported"
+                );
+                members.save(storage, member, &added_at)?;
+            }
+            ExportEntry::Allowance {
+                owner,
+                spender,
+                chain_id,
+                amount,
+            } => {
+                let key = (owner, spender, chain_id);
+                assert!(
+                    !ALLOWANCES.has(storage, key.clone()),
+                    "Entry already imported"
+                );
+                ALLOWANCES.save(storage, key, &amount)?;
+            }
+            ExportEntry::MintRequest { id, mint_request } => {
+                let last_id = LAST_MINT_REQUEST_ID.may_load(storage)?.unwrap_or_default();
+                assert!(id > 0 && id <= last_id, "Id out of range");
+                assert!(!MINT_REQUESTS.has(storage, id), "Entry already imported");
+                MINT_REQUESTS.save(storage, id, &mint_request)?;
+            }
+            ExportEntry::Redemption { id, redemption } => {
+                let last_id = LAST_REDEMPTION_ID.may_load(storage)?.unwrap_or_default();
+                assert!(id > 0 && id <= last_id, "Id out of range");
+                assert!(!REDEMPTIONS.has(storage, id), "Entry already imported");
+                if let Some(reference) = &redemption.reference {
+                    REDEMPTION_REFERENCES.save(storage, reference.clone(), &id)?;
+                }
+                REDEMPTIONS.save(storage, id, &redemption)?;
+            }
+        }
+    }
+    IMPORT_PROGRESS.update(storage, |mut progress| -> StdResult<_> {
+        progress.imported_entries += count;
+        Ok(progress)
+    })?;
+    Ok(count)
+}
+
+// Compliance lists in export order
+const EXPORT_COMPLIANCE_LISTS: [ComplianceList; 3] = [
+    ComplianceList::SenderDenylist,
+    ComplianceList::RecipientDenylist,
+    ComplianceList::Allowlist,
+];
+
+// Position of the section of a cursor in export order
+fn export_section(cursor: &ExportCursor) -> usize {
+    match cursor {
+        ExportCursor::Withdrawal(_) => 0,
+        ExportCursor::ComplianceMember(list, _) => {
+            1 + EXPORT_COMPLIANCE_LISTS
+                .iter()
+                .position(|export_list| export_list == list)
+                .unwrap_or_default()
+        }
+        ExportCursor::Allowance(..) => 4,
+        ExportCursor::MintRequest(_) => 5,
+        ExportCursor::Redemption(_) => 6,
+    }
+}
+
+fn export_cursor(entry: &ExportEntry) -> ExportCursor {
+    match entry {
+        ExportEntry::Withdrawal { nonce, .. } => ExportCursor::Withdrawal(*nonce),
+        ExportEntry::ComplianceMember { list, member, .. } => {
+            ExportCursor::ComplianceMember(list.clone(), member.clone())
+        }
+        ExportEntry::Allowance {
+            owner,
+            spender,
+            chain_id,
+            ..
+        } => ExportCursor::Allowance(owner.clone(), spender.clone(), chain_id.clone()),
+        ExportEntry::MintRequest { id, .. } => ExportCursor::MintRequest(*id),
+        ExportEntry::Redemption { id, .. } => ExportCursor::Redemption(*id),
+    }
+}
+
+// Up to limit entries after start_after, walking withdrawals, compliance lists, allowances,
+// mint requests and redemptions in that order, with the cursor of the next page
+fn export_entries(
+    storage: &dyn Storage,
+    start_after: Option<ExportCursor>,
+    limit: usize,
+) -> StdResult<(Vec<ExportEntry>, Option<ExportCursor>)> {
+    let section = start_after.as_ref().map_or(0, export_section);
+    // One entry more than the page tells whether another page follows
+    let take = limit + 1;
+    let mut entries = vec![];
+    if section == 0 {
+        let start = match &start_after {
+            Some(ExportCursor::Withdrawal(nonce)) => Some(Bound::exclusive(*nonce)),
+            _ => None,
+        };
+        for item in WITHDRAW_LIST
+            .range(storage, start, None, Order::Ascending)
+            .take(take)
+        {
+            let (nonce, burn_info) = item?;
+            entries.push(ExportEntry::Withdrawal { nonce, burn_info });
+        }
+    }
+    for (index, list) in EXPORT_COMPLIANCE_LISTS.iter().enumerate() {
+        if section > 1 + index {
+            continue;
+        }
+        let start = match &start_after {
+            Some(ExportCursor::ComplianceMember(cursor_list, member)) if cursor_list == list => {
+                Some(Bound::exclusive(member.clone()))
+            }
+            _ => None,
+        };
+        for item in list
+            .members()
+            .range(storage, start, None, Order::Ascending)
+            .take(take - entries.len())
+        {
+            let (member, added_at) = item?;
+            entries.push(ExportEntry::ComplianceMember {
+                list: list.clone(),
+                member,
+                added_at,
+            });
+        }
+    }
+    if section <= 4 {
+        let start =
+            match &start_after {
+                Some(ExportCursor::Allowance(owner, spender, chain_id)) => Some(Bound::exclusive(
+                    (owner.clone(), spender.clone(), chain_id.clone()),
+                )),
+                _ => None,
+            };
+        for item in ALLOWANCES
+            .range(storage, start, None, Order::Ascending)
+            .take(take - entries.len())
+        {
+            let ((owner, spender, chain_id), amount) = item?;
+            entries.push(ExportEntry::Allowance {
+                owner,
+                spender,
+                chain_id,
+                amount,
+            });
+        }
+    }
+    if section <= 5 {
+        let start = match &start_after {
+            Some(ExportCursor::MintRequest(id)) => Some(Bound::exclusive(*id)),
+            _ => None,
+        };
+        for item in MINT_REQUESTS
+            .range(storage, start, None, Order::Ascending)
+            .take(take - entries.len())
+        {
+            let (id, mint_request) = item?;
+            entries.push(ExportEntry::MintRequest { id, mint_request });
+        }
+    }
+    let start = match &start_after {
+        Some(ExportCursor::Redemption(id)) => Some(Bound::exclusive(*id)),
+        _ => None,
+    };
+    for item in REDEMPTIONS
+        .range(storage, start, None, Order::Ascending)
+        .take(take - entries.len())
+    {
+        let (id, redemption) = item?;
+        entries.push(ExportEntry::Redemption { id, redemption });
+    }
+    if entries.len() > limit {
+        entries.truncate(limit);
+        return Ok((entries.clone(), entries.last().map(export_cursor)));
+    }
+    Ok((entries, None))
+}
+
+// Number of entries of an export over all pages
+fn export_entry_count(storage: &dyn Storage) -> StdResult<u64> {
+    let mut count = WITHDRAW_LIST
+        .keys(storage, None, None, Order::Ascending)
+        .count();
+    for list in &EXPORT_COMPLIANCE_LISTS {
+        count += list
+            .members()
+            .keys(storage, None, None, Order::Ascending)
+            .count();
+    }
+    count += ALLOWANCES
+        .keys(storage, None, None, Order::Ascending)
+        .count();
+    count += MINT_REQUESTS
+        .keys(storage, None, None, Order::Ascending)
+        .count();
+    count += REDEMPTIONS
+        .keys(storage, None, None, Order::Ascending)
+        .count();
+    Ok(count as u64)
+}
+
+fn update_accounting<F>(storage: &mut dyn Storage, action: F) -> StdResult<()>
+where
+    F: FnOnce(&mut Accounting) -> StdResult<()>,
+{
+    let mut accounting = ACCOUNTING.may_load(storage)?.unwrap_or_default();
+    action(&mut accounting)?;
+    ACCOUNTING.save(storage, &accounting)
+}
+
+// Send the PUSD of an approved mint request and record it as minted
+fn execute_mint_request(
+    deps: DepsMut<PalomaQuery>,
+    env: &Env,
+    response: Response<PalomaMsg>,
+    mint_request: &MintRequest,
+) -> Result<Response<PalomaMsg>, ContractError> {
+    // The recipient may have been denylisted since the proposal
+    assert_sender_allowed(deps.storage, &mint_request.recipient);
+    let denom = STATE.load(deps.storage)?.denom;
+    assert_inventory(deps.as_ref(), env, &denom, mint_request.amount)?;
+    update_accounting(deps.storage, |accounting| {
+        accounting.total_minted += mint_request.amount;
+        Ok(())
+    })?;
+    Ok(response
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: mint_request.recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount: mint_request.amount,
+            }],
+        }))
+        .add_event(PusdEvent::Mint {
+            recipient: mint_request.recipient.clone(),
+            amount: mint_request.amount,
+            denom,
+        }))
+}
+
+// Refuse sending out or burning PUSD that backs pending withdrawals or the keeper pool
+fn assert_inventory(
+    deps: Deps<PalomaQuery>,
+    env: &Env,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let accounting = ACCOUNTING.may_load(deps.storage)?.unwrap_or_default();
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address.clone(), denom)?
+        .amount;
+    let inventory = balance.saturating_sub(accounting.total_escrowed + accounting.keeper_pool);
+    assert!(amount <= inventory, "Insufficient mint inventory");
+    Ok(())
+}
+
+// Whether the retry policy of the chain allows no more retries of a withdraw
+fn attempts_exhausted(chain_setting: &ChainSetting, burn_info: &BurnInfo) -> bool {
+    chain_setting
+        .retry_policy
+        .as_ref()
+        .is_some_and(|retry_policy| burn_info.attempts >= retry_policy.max_attempts)
+}
+
+// Stop dispatching duplicate jobs for a withdraw until the owner resets its retries
+fn mark_needs_review(
+    storage: &mut dyn Storage,
+    nonce: u64,
+    mut burn_info: BurnInfo,
+) -> StdResult<PusdEvent> {
+    burn_info.status = WithdrawStatus::NeedsReview;
+    WITHDRAW_LIST.save(storage, nonce, &burn_info)?;
+    Ok(PusdEvent::NeedsReview {
+        nonce,
+        chain_id: burn_info.chain_id,
+        attempts: burn_info.attempts,
+    })
+}
+
+// Job re-sending a pending withdraw for its burner, counted as an attempt
+fn dispatch_retry(
+    deps: DepsMut<PalomaQuery>,
+    env: &Env,
+    nonce: u64,
+    mut burn_info: BurnInfo,
+    chain_setting: &ChainSetting,
+) -> StdResult<(CosmosMsg<PalomaMsg>, PusdEvent)> {
+    let remote_amount = burn_info
+        .remote_amount
+        .unwrap_or_else(|| Uint256::from(burn_info.amount));
+    let payload = withdraw_payload(
+        deps.as_ref(),
+        &burn_info.burner,
+        &burn_info.recipient,
+        remote_amount,
+        nonce,
+    )?;
+    burn_info.timestamp = env.block.time;
+    burn_info.attempts += 1;
+    WITHDRAW_LIST.save(deps.storage, nonce, &burn_info)?;
+    let job = scheduler_job(deps.as_ref(), chain_setting, JobKind::Withdraw, payload);
+    Ok((
+        job,
+        PusdEvent::ReWithdraw {
+            nonce,
+            chain_id: burn_info.chain_id,
+            burner: burn_info.burner,
+            recipient: burn_info.recipient,
+            remote_amount,
+            attempt: burn_info.attempts,
+        },
+    ))
+}
+
+// Delay before the next retry of a withdraw that was retried attempts times
+fn retry_delay(state: &State, chain_setting: &ChainSetting, attempts: u32) -> u64 {
+    match &chain_setting.retry_policy {
+        Some(retry_policy) => u64::from(retry_policy.multiplier)
+            .saturating_pow(attempts)
+            .saturating_mul(retry_policy.base_delay)
+            .min(retry_policy.max_delay),
+        None => state.retry_delay,
+    }
+}
+
+// Scheduler jobs of a chain
+enum JobKind {
+    // Withdraw calls of users
+    Withdraw,
+    // Configuration calls of the owner, run by the admin job if the chain has one
+    Admin,
+}
+
+// Message executing the job of the given kind of a chain with payload. The job may have
+// been removed from the scheduler since the chain was registered.
+fn scheduler_job(
+    deps: Deps<PalomaQuery>,
+    chain_setting: &ChainSetting,
+    kind: JobKind,
+    payload: Binary,
+) -> CosmosMsg<PalomaMsg> {
+    let job_id = match kind {
+        JobKind::Withdraw => chain_setting.job_id.clone(),
+        JobKind::Admin => chain_setting
+            .admin_job_id
+            .clone()
+            .unwrap_or_else(|| chain_setting.job_id.clone()),
+    };
+    assert!(job_exists(deps, &job_id), "Job not found");
+    let job_hints = chain_setting.job_hints.clone().unwrap_or_default();
+    CosmosMsg::Custom(PalomaMsg::SchedulerMsg(SchedulerMsg::ExecuteJob(
+        ExecuteJob {
+            job_id,
+            payload,
+            gas_limit: job_hints.gas_limit,
+            priority: job_hints.priority,
+        },
+    )))
+}
+
+// Refuse chain settings whose jobs are unknown to the scheduler
+fn assert_jobs_exist(deps: Deps<PalomaQuery>, chain_setting: &ChainSetting) {
+    assert!(job_exists(deps, &chain_setting.job_id), "Job not found");
+    if let Some(admin_job_id) = &chain_setting.admin_job_id {
+        assert!(job_exists(deps, admin_job_id), "Admin job not found");
+    }
+}
+
+fn job_exists(deps: Deps<PalomaQuery>, job_id: &str) -> bool {
+    // The scheduler fails the query for unknown jobs
+    deps.querier
+        .query::<JobResponse>(&QueryRequest::Custom(PalomaQuery::Scheduler(
+            SchedulerQuery::GetJobById {
+                job_id: job_id.to_string(),
+            },
+        )))
+        .is_ok()
+}
+
+// Whether Paloma relays to the chain
+fn chain_active(deps: Deps<PalomaQuery>, chain_reference_id: &str) -> StdResult<bool> {
+    let chain_info: ChainInfoResponse = deps.querier.query(&QueryRequest::Custom(
+        PalomaQuery::Evm(EvmQuery::GetChainInfo {
+            chain_reference_id: chain_reference_id.to_string(),
+        }),
+    ))?;
+    Ok(chain_info.active)
+}
+
+// Refuse chain settings that can't be applied
+fn assert_chain_setting(chain_setting: &ChainSetting) {
+    assert!(!chain_setting.job_id.is_empty(), "Job ID cannot be empty");
+    if let Some(admin_job_id) = &chain_setting.admin_job_id {
+        assert!(!admin_job_id.is_empty(), "Admin job ID cannot be empty");
+        assert!(
+            *admin_job_id != chain_setting.job_id,
+            "Admin job ID must differ from job ID"
+        );
+    }
+    // Rewarded retries of a withdraw have to end in review
+    assert!(
+        chain_setting.keeper_reward.is_none() || chain_setting.retry_policy.is_some(),
+        "Keeper reward requires a retry policy"
+    );
+    if let Some(retry_policy) = &chain_setting.retry_policy {
+        assert!(
+            retry_policy.multiplier > 0,
+            "Retry multiplier must be greater than 0"
+        );
+        assert!(
+            retry_policy.base_delay <= retry_policy.max_delay,
+            "Retry base delay exceeds max delay"
+        );
+    }
+}
+
+// Whether a pending withdraw is past the expiry window of its chain
+fn is_expired(chain_setting: &ChainSetting, burn_info: &BurnInfo, env: &Env) -> bool {
+    chain_setting.expiry_window.is_some_and(|expiry_window| {
+        burn_info.created_at().plus_seconds(expiry_window) <= env.block.time
+    })
+}
+
+// Whether the last job of a withdraw may still be executed on the remote chain. Without
+// chain settings, after a forced deregister, the flat retry delay applies.
+fn in_flight(
+    state: &State,
+    chain_setting: Option<&ChainSetting>,
+    burn_info: &BurnInfo,
+    env: &Env,
+) -> bool {
+    let delay = chain_setting.map_or(state.retry_delay, |chain_setting| {
+        retry_delay(state, chain_setting, burn_info.attempts)
+    });
+    burn_info.timestamp.plus_seconds(delay) >= env.block.time
+}
+
+// Oldest expired withdrawals of a chain after start_after that are no longer in flight,
+// with the nonce to continue after. Skyway transfers are left to CancelWithdraw, as
+// cancelling a relayed one fails.
+fn expired_withdrawals(
+    deps: Deps<PalomaQuery>,
+    env: &Env,
+    chain_id: &str,
+    chain_setting: &ChainSetting,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ScannedWithdrawals> {
+    let state = STATE.load(deps.storage)?;
+    scan_withdrawals(
+        deps.storage,
+        chain_id,
+        start_after,
+        limit,
+        |_, burn_info| {
+            // Nonces are created in order, so the withdrawals after it expire later
+            if !is_expired(chain_setting, burn_info, env) {
+                Scan::Stop
+            } else if burn_info.transport == Transport::Compass
+                && !in_flight(&state, Some(chain_setting), burn_info, env)
+            {
+                Scan::Take
+            } else {
+                Scan::Skip
+            }
+        },
+    )
+}
+
+// Picked withdrawals with the nonce to continue after
+type ScannedWithdrawals = (Vec<(u64, BurnInfo)>, Option<u64>);
+
+enum Scan {
+    Take,
+    Skip,
+    Stop,
+}
+
+// Pending withdrawals of a chain after start_after picked by select, at most limit of
+// them out of at most MAX_SCAN looked at. Returns the last nonce looked at when the scan
+// stopped before the end of the chain.
+fn scan_withdrawals(
+    storage: &dyn Storage,
+    chain_id: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    mut select: impl FnMut(&dyn Storage, &BurnInfo) -> Scan,
+) -> StdResult<ScannedWithdrawals> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    let mut selected = vec![];
+    let mut scanned = 0;
+    for nonce in CHAIN_WITHDRAWALS.prefix(chain_id.to_string()).keys(
+        storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    ) {
+        let nonce = nonce?;
+        let burn_info = WITHDRAW_LIST.load(storage, nonce)?;
+        match select(storage, &burn_info) {
+            Scan::Take => selected.push((nonce, burn_info)),
+            Scan::Skip => {}
+            Scan::Stop => return Ok((selected, None)),
+        }
+        scanned += 1;
+        if selected.len() == limit || scanned == MAX_SCAN {
+            return Ok((selected, Some(nonce)));
+        }
+    }
+    Ok((selected, None))
+}
+
+// Remove a pending withdraw and release its escrow to the refund address, or the burner.
+// A Skyway transfer is cancelled first, which returns the PUSD to the contract.
+fn refund_withdraw(
+    storage: &mut dyn Storage,
+    nonce: u64,
+    burn_info: &BurnInfo,
+) -> Result<(Addr, Vec<CosmosMsg<PalomaMsg>>), ContractError> {
+    let amount = Uint128::from(burn_info.amount);
+    WITHDRAW_LIST.remove(storage, nonce);
+    CHAIN_WITHDRAWALS.remove(storage, (burn_info.chain_id.clone(), nonce));
+    CHAIN_ACCOUNTING.update(
+        storage,
+        burn_info.chain_id.clone(),
+        |chain_accounting| -> StdResult<_> {
+            let mut chain_accounting = chain_accounting.unwrap_or_default();
+            chain_accounting.total_cancelled += amount;
+            Ok(chain_accounting)
+        },
+    )?;
+    let mut messages = vec![];
+    match burn_info.transport {
+        Transport::Compass => update_accounting(storage, |accounting| {
+            accounting.total_escrowed = accounting.total_escrowed.checked_sub(amount)?;
+            Ok(())
+        })?,
+        Transport::Skyway => {
+            let transaction_id = burn_info
+                .skyway_tx_id
+                .expect("Skyway transaction not confirmed");
+            update_accounting(storage, |accounting| {
+                accounting.total_in_transit = accounting.total_in_transit.checked_sub(amount)?;
+                Ok(())
+            })?;
+            messages.push(CosmosMsg::Custom(PalomaMsg::SkywayMsg(
+                SkywayMsg::CancelTx(CancelTx { transaction_id }),
+            )));
+        }
+    }
+    let refund_address = burn_info
+        .refund_address
+        .clone()
+        .unwrap_or_else(|| burn_info.burner.clone());
+    messages.push(CosmosMsg::Bank(BankMsg::Send {
+        to_address: refund_address.to_string(),
+        amount: vec![Coin {
+            denom: STATE.load(storage)?.denom,
+            amount,
+        }],
+    }));
+    Ok((refund_address, messages))
+}
+
+// Validated key of a compliance list member, EVM addresses are stored lowercase
+fn compliance_member(
+    deps: Deps<PalomaQuery>,
+    list: &ComplianceList,
+    member: &str,
+) -> StdResult<String> {
+    match list {
+        ComplianceList::RecipientDenylist => {
+            assert!(Address::from_str(member).is_ok(), "Invalid EVM address");
+            Ok(member.to_lowercase())
+        }
+        ComplianceList::SenderDenylist | ComplianceList::Allowlist => {
+            Ok(deps.api.addr_validate(member)?.to_string())
+        }
+    }
+}
+
+// Count a withdraw against the quota of the burner on the chain over the rolling day
+fn consume_quota(
+    storage: &mut dyn Storage,
+    env: &Env,
+    chain_id: &str,
+    chain_setting: &ChainSetting,
+    burner: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let Some(quota) = &chain_setting.quota else {
+        return Ok(());
+    };
+    if ALLOWLIST.has(storage, burner.to_string()) {
+        return Ok(());
+    }
+    let (usage, _) = quota_usage(storage, env, burner.as_str(), chain_id)?;
+    if let Some(max_amount) = quota.max_amount {
+        assert!(
+            usage.amount + amount <= max_amount,
+            "Daily withdraw amount exceeded"
+        );
+    }
+    if let Some(max_count) = quota.max_count {
+        assert!(usage.count < max_count, "Daily withdraw count exceeded");
+    }
+
+    // Buckets that left the rolling day no longer count
+    let stale = QUOTA_USAGE
+        .prefix((burner.to_string(), chain_id.to_string()))
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(first_quota_bucket(env))),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<u64>>>()?;
+    for bucket in stale {
+        QUOTA_USAGE.remove(storage, (burner.to_string(), chain_id.to_string(), bucket));
+    }
+    QUOTA_USAGE.update(
+        storage,
+        (
+            burner.to_string(),
+            chain_id.to_string(),
+            env.block.time.seconds() / QUOTA_BUCKET,
+        ),
+        |usage| -> StdResult<_> {
+            let mut usage = usage.unwrap_or_default();
+            usage.amount += amount;
+            usage.count += 1;
+            Ok(usage)
+        },
+    )?;
+    Ok(())
+}
+
+// Oldest quota bucket of the rolling day
+fn first_quota_bucket(env: &Env) -> u64 {
+    (env.block.time.seconds() / QUOTA_BUCKET).saturating_sub(QUOTA_BUCKETS - 1)
+}
+
+// Usage of a burner on a chain over the rolling day, with the oldest bucket it was counted in
+fn quota_usage(
+    storage: &dyn Storage,
+    env: &Env,
+    burner: &str,
+    chain_id: &str,
+) -> StdResult<(QuotaUsage, Option<u64>)> {
+    let mut usage = QuotaUsage::default();
+    let mut oldest_bucket = None;
+    for item in QUOTA_USAGE
+        .prefix((burner.to_string(), chain_id.to_string()))
+        .range(
+            storage,
+            Some(Bound::inclusive(first_quota_bucket(env))),
+            None,
+            Order::Ascending,
+        )
+    {
+        let (bucket, bucket_usage) = item?;
+        oldest_bucket.get_or_insert(bucket);
+        usage.amount += bucket_usage.amount;
+        usage.count += bucket_usage.count;
+    }
+    Ok((usage, oldest_bucket))
+}
+
+fn is_compliant(
+    storage: &dyn Storage,
+    chain_setting: &ChainSetting,
+    sender: &Addr,
+    recipient: &str,
+) -> bool {
+    !SENDER_DENYLIST.has(storage, sender.to_string())
+        && !RECIPIENT_DENYLIST.has(storage, recipient.to_lowercase())
+        && (!chain_setting.allowlist_only || ALLOWLIST.has(storage, sender.to_string()))
+}
+
+// Refuse denylisted senders and recipients, and senders missing from the allowlist
+// of an allowlist only chain
+fn assert_compliant(
+    storage: &dyn Storage,
+    chain_setting: &ChainSetting,
+    sender: &Addr,
+    recipient: &str,
+) {
+    assert_sender_allowed(storage, sender);
+    assert!(
+        !RECIPIENT_DENYLIST.has(storage, recipient.to_lowercase()),
+        "Recipient is denylisted"
+    );
+    assert!(
+        !chain_setting.allowlist_only || ALLOWLIST.has(storage, sender.to_string()),
+        "Account is not allowlisted"
+    );
+}
+
+// Refuse accounts on the sender denylist, which also can't receive minted PUSD
+fn assert_sender_allowed(storage: &dyn Storage, account: &Addr) {
+    assert!(
+        !SENDER_DENYLIST.has(storage, account.to_string()),
+        "Account is denylisted"
+    );
+}
+
+// Next sequence number of a per-key history map
+fn next_history_index<T>(
+    storage: &dyn Storage,
+    history: &Map<(String, u64), T>,
+    key: &str,
+) -> StdResult<u64>
+where
+    T: Serialize + DeserializeOwned,
+{
+    Ok(history
+        .prefix(key.to_string())
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |index| index + 1))
+}
+
+fn record_chain_setting_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    chain_id: &str,
+    action: ChainSettingAction,
+    chain_setting: Option<ChainSetting>,
+) -> StdResult<()> {
+    let index = next_history_index(storage, &CHAIN_SETTING_HISTORY, chain_id)?;
+    CHAIN_SETTING_HISTORY.save(
+        storage,
+        (chain_id.to_string(), index),
+        &ChainSettingChange {
+            action,
+            chain_setting,
+            sender: sender.clone(),
+            timestamp: env.block.time,
+        },
+    )
+}
+
+// Scale a local amount to the remote token decimals.
+// Returns the remote amount and the local remainder that can't be represented remotely.
+fn to_remote_amount(
+    amount: Uint128,
+    local_decimals: u8,
+    remote_decimals: u8,
+) -> StdResult<(Uint256, Uint128)> {
+    if remote_decimals >= local_decimals {
+        let factor = Uint256::from(10u8).checked_pow((remote_decimals - local_decimals).into())?;
+        Ok((Uint256::from(amount).checked_mul(factor)?, Uint128::zero()))
+    } else {
+        let factor = Uint128::from(10u8).checked_pow((local_decimals - remote_decimals).into())?;
+        Ok((
+            Uint256::from(amount.checked_div(factor)?),
+            amount.checked_rem(factor)?,
+        ))
+    }
+}
+
+// ABI encoded call of the Vyper contract's withdraw function
+fn withdraw_payload(
+    deps: Deps<PalomaQuery>,
+    sender: &Addr,
+    recipient: &str,
+    amount: Uint256,
+    nonce: u64,
+) -> StdResult<Binary> {
+    #[allow(deprecated)]
+    let contract: Contract = Contract {
+        constructor: None,
+        functions: BTreeMap::from_iter(vec![(
+            "withdraw".to_string(),
+            vec![Function {
+                name: "withdraw".to_string(),
+                inputs: vec![
+                    Param {
+                        name: "sender".to_string(),
+                        kind: ParamType::FixedBytes(32),
+                        internal_type: None,
+                    },
+                    Param {
+                        name: "recipient".to_string(),
+                        kind: ParamType::Address,
+                        internal_type: None,
+                    },
+                    Param {
+                        name: "amount".to_string(),
+                        kind: ParamType::Uint(256),
+                        internal_type: None,
+                    },
+                    Param {
+                        name: "nonce".to_string(),
+                        kind: ParamType::Uint(256),
+                        internal_type: None,
+                    },
+                ],
+                outputs: Vec::new(),
+                constant: None,
+                state_mutability: StateMutability::NonPayable,
+            }],
+        )]),
+        events: BTreeMap::new(),
+        errors: BTreeMap::new(),
+        receive: false,
+        fallback: false,
+    };
+    let canonical_addr: CanonicalAddr = deps.api.addr_canonicalize(sender.as_str())?;
+    let tokens = &[
+        Token::FixedBytes(canonical_addr.as_slice().to_vec()),
+        Token::Address(Address::from_str(recipient).unwrap()),
+        Token::Uint(Uint::from_big_endian(&amount.to_be_bytes())),
+        Token::Uint(Uint::from_big_endian(&nonce.to_be_bytes())),
+    ];
+    Ok(Binary::new(
+        contract
+            .function("withdraw")
+            .unwrap()
+            .encode_input(tokens.as_slice())
+            .unwrap(),
+    ))
+}
@@ -11,6 +11,12 @@ pub enum ContractError {
 
     #[error("Migration failed")]
     MigrationFailed {},
+
+    #[error("Cannot migrate from contract {stored}")]
+    InvalidContractName { stored: String },
+
+    #[error("Cannot downgrade from {stored} to {current}")]
+    CannotDowngrade { stored: String, current: String },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
@@ -0,0 +1,724 @@
+use cosmwasm_std::{Addr, Event, Response, Uint128, Uint256};
+
+use crate::state::Transport;
+
+/// Every action of the contract emits one `pusd_<action>` wasm event with the
+/// attributes listed on its variant, in that order. Attributes of `Option`
+/// fields are only present when set. The response also carries the `action`
+/// attribute for filtering on the message level.
+pub enum PusdEvent {
+    /// `pusd_instantiate`: owner, minter, denom, decimals, retry_delay
+    Instantiate {
+        owner: Addr,
+        minter: Addr,
+        denom: String,
+        decimals: u8,
+        retry_delay: u64,
+    },
+    /// `pusd_import_state`: version, owner, chain_count, entry_count, expected_entries
+    ImportState {
+        version: u32,
+        owner: Addr,
+        chain_count: u64,
+        entry_count: u64,
+        expected_entries: u64,
+    },
+    /// `pusd_import_entries`: count
+    ImportEntries { count: u64 },
+    /// `pusd_finalize_import`: entry_count, withdrawal_count, total_escrowed
+    FinalizeImport {
+        entry_count: u64,
+        withdrawal_count: u64,
+        total_escrowed: Uint128,
+    },
+    /// `pusd_migrate`: from_version, version, steps, minter (optional)
+    Migrate {
+        from_version: String,
+        version: String,
+        steps: Vec<&'static str>,
+        minter: Option<Addr>,
+    },
+    /// `pusd_register_chain`: chain_id, job_id, admin_job_id (optional), minimum_amount,
+    /// decimals (optional)
+    RegisterChain {
+        chain_id: String,
+        job_id: String,
+        admin_job_id: Option<String>,
+        minimum_amount: Uint128,
+        decimals: Option<u8>,
+    },
+    /// `pusd_update_chain`: chain_id, job_id, admin_job_id (optional), minimum_amount,
+    /// decimals (optional)
+    UpdateChain {
+        chain_id: String,
+        job_id: String,
+        admin_job_id: Option<String>,
+        minimum_amount: Uint128,
+        decimals: Option<u8>,
+    },
+    /// `pusd_deregister_chain`: chain_id, pending_count
+    DeregisterChain {
+        chain_id: String,
+        pending_count: u64,
+    },
+    /// `pusd_set_bridge`: chain_reference_id, erc20_address, denom
+    SetBridge {
+        chain_reference_id: String,
+        erc20_address: String,
+        denom: String,
+    },
+    /// `pusd_update_config`: retry_delay (optional), owner (optional), timelock_delay (optional)
+    UpdateConfig {
+        retry_delay: Option<u64>,
+        owner: Option<Addr>,
+        timelock_delay: Option<u64>,
+    },
+    /// `pusd_update_owner`: owner
+    UpdateOwner { owner: Addr },
+    /// `pusd_update_timelock_delay`: timelock_delay
+    UpdateTimelockDelay { timelock_delay: u64 },
+    /// `pusd_propose_admin_action`: id, admin_action, eta
+    ProposeAdminAction {
+        id: u64,
+        admin_action: String,
+        eta: u64,
+    },
+    /// `pusd_execute_admin_action`: id, admin_action
+    ExecuteAdminAction { id: u64, admin_action: String },
+    /// `pusd_cancel_admin_action`: id
+    CancelAdminAction { id: u64 },
+    /// `pusd_update_mint_approval_config`: enabled, threshold (optional), minimum_amount (optional)
+    UpdateMintApprovalConfig {
+        enabled: bool,
+        threshold: Option<u32>,
+        minimum_amount: Option<Uint128>,
+    },
+    /// `pusd_propose_mint`: id, proposer, recipient, amount, executed
+    ProposeMint {
+        id: u64,
+        proposer: Addr,
+        recipient: Addr,
+        amount: Uint128,
+        executed: bool,
+    },
+    /// `pusd_approve_mint`: id, approver, approvals, executed
+    ApproveMint {
+        id: u64,
+        approver: Addr,
+        approvals: u32,
+        executed: bool,
+    },
+    /// `pusd_cancel_mint`: id
+    CancelMint { id: u64 },
+    /// `pusd_mint`: recipient, amount, denom
+    Mint {
+        recipient: Addr,
+        amount: Uint128,
+        denom: String,
+    },
+    /// `pusd_unmint`: redemption_id, minter, amount, denom, burned, reference (optional)
+    Unmint {
+        redemption_id: u64,
+        minter: Addr,
+        amount: Uint128,
+        denom: String,
+        burned: bool,
+        reference: Option<String>,
+    },
+    /// `pusd_withdraw`: nonce, chain_id, burner, recipient, amount, remote_amount, dust, denom,
+    /// transport, operator (optional), refund_address (optional)
+    Withdraw {
+        nonce: u64,
+        chain_id: String,
+        burner: Addr,
+        recipient: String,
+        amount: Uint128,
+        remote_amount: Uint256,
+        dust: Uint128,
+        denom: String,
+        transport: Transport,
+        operator: Option<Addr>,
+        refund_address: Option<Addr>,
+    },
+    /// `pusd_skyway_send`: nonce, chain_id, transaction_id
+    SkywaySend {
+        nonce: u64,
+        chain_id: String,
+        transaction_id: u64,
+    },
+    /// `pusd_grant_allowance`: owner, spender, chain_id, amount
+    GrantAllowance {
+        owner: Addr,
+        spender: Addr,
+        chain_id: String,
+        amount: Uint128,
+    },
+    /// `pusd_revoke_allowance`: owner, spender, chain_id
+    RevokeAllowance {
+        owner: Addr,
+        spender: Addr,
+        chain_id: String,
+    },
+    /// `pusd_re_withdraw`: nonce, chain_id, burner, recipient, remote_amount, attempt
+    ReWithdraw {
+        nonce: u64,
+        chain_id: String,
+        burner: Addr,
+        recipient: String,
+        remote_amount: Uint256,
+        attempt: u32,
+    },
+    /// `pusd_needs_review`: nonce, chain_id, attempts
+    NeedsReview {
+        nonce: u64,
+        chain_id: String,
+        attempts: u32,
+    },
+    /// `pusd_retry_batch`: chain_id, nonces (comma separated), keeper, reward, denom,
+    /// next_start_after (when the scan stopped early)
+    RetryBatch {
+        chain_id: String,
+        nonces: Vec<u64>,
+        keeper: Addr,
+        reward: Uint128,
+        denom: String,
+        next_start_after: Option<u64>,
+    },
+    /// `pusd_fund_keeper_pool`: sender, amount, keeper_pool
+    FundKeeperPool {
+        sender: Addr,
+        amount: Uint128,
+        keeper_pool: Uint128,
+    },
+    /// `pusd_reset_retries`: nonce, chain_id
+    ResetRetries { nonce: u64, chain_id: String },
+    /// `pusd_burn`: nonce, chain_id, burner, amount, denom
+    Burn {
+        nonce: u64,
+        chain_id: String,
+        burner: Addr,
+        amount: Uint128,
+        denom: String,
+    },
+    /// `pusd_cancel_withdraw`: nonce, chain_id, burner, recipient, amount, denom, refund_address
+    CancelWithdraw {
+        nonce: u64,
+        chain_id: String,
+        burner: Addr,
+        recipient: String,
+        amount: Uint128,
+        denom: String,
+        refund_address: Addr,
+    },
+    /// `pusd_refund_expired`: chain_id, nonces (comma separated), amount, denom, sender,
+    /// next_start_after (when the scan stopped early)
+    RefundExpired {
+        chain_id: String,
+        nonces: Vec<u64>,
+        amount: Uint128,
+        denom: String,
+        sender: Addr,
+        next_start_after: Option<u64>,
+    },
+    /// `pusd_force_refund`: nonce, chain_id, burner, amount, denom, refund_address, sender
+    ForceRefund {
+        nonce: u64,
+        chain_id: String,
+        burner: Addr,
+        amount: Uint128,
+        denom: String,
+        refund_address: Addr,
+        sender: Addr,
+    },
+    /// `pusd_update_compliance_list`: list, added, removed (comma separated members)
+    UpdateComplianceList {
+        list: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// `pusd_set_paloma`: chain_id
+    SetPaloma { chain_id: String },
+    /// `pusd_update_compass`: chain_id, new_compass
+    UpdateCompass {
+        chain_id: String,
+        new_compass: String,
+    },
+    /// `pusd_update_refund_wallet`: chain_id, new_refund_wallet
+    UpdateRefundWallet {
+        chain_id: String,
+        new_refund_wallet: String,
+    },
+    /// `pusd_update_redemption_fee`: chain_id, new_redemption_fee
+    UpdateRedemptionFee {
+        chain_id: String,
+        new_redemption_fee: Uint128,
+    },
+}
+
+impl PusdEvent {
+    pub fn action(&self) -> &'static str {
+        match self {
+            PusdEvent::Instantiate { .. } => "instantiate",
+            PusdEvent::ImportState { .. } => "import_state",
+            PusdEvent::ImportEntries { .. } => "import_entries",
+            PusdEvent::FinalizeImport { .. } => "finalize_import",
+            PusdEvent::Migrate { .. } => "migrate",
+            PusdEvent::RegisterChain { .. } => "register_chain",
+            PusdEvent::UpdateChain { .. } => "update_chain",
+            PusdEvent::DeregisterChain { .. } => "deregister_chain",
+            PusdEvent::SetBridge { .. } => "set_bridge",
+            PusdEvent::UpdateConfig { .. } => "update_config",
+            PusdEvent::UpdateOwner { .. } => "update_owner",
+            PusdEvent::UpdateTimelockDelay { .. } => "update_timelock_delay",
+            PusdEvent::ProposeAdminAction { .. } => "propose_admin_action",
+            PusdEvent::ExecuteAdminAction { .. } => "execute_admin_action",
+            PusdEvent::CancelAdminAction { .. } => "cancel_admin_action",
+            PusdEvent::UpdateMintApprovalConfig { .. } => "update_mint_approval_config",
+            PusdEvent::ProposeMint { .. } => "propose_mint",
+            PusdEvent::ApproveMint { .. } => "approve_mint",
+            PusdEvent::CancelMint { .. } => "cancel_mint",
+            PusdEvent::Mint { .. } => "mint",
+            PusdEvent::Unmint { .. } => "unmint",
+            PusdEvent::Withdraw { .. } => "withdraw",
+            PusdEvent::SkywaySend { .. } => "skyway_send",
+            PusdEvent::GrantAllowance { .. } => "grant_allowance",
+            PusdEvent::RevokeAllowance { .. } => "revoke_allowance",
+            PusdEvent::ReWithdraw { .. } => "re_withdraw",
+            PusdEvent::NeedsReview { .. } => "needs_review",
+            PusdEvent::RetryBatch { .. } => "retry_batch",
+            PusdEvent::FundKeeperPool { .. } => "fund_keeper_pool",
+            PusdEvent::ResetRetries { .. } => "reset_retries",
+            PusdEvent::Burn { .. } => "burn",
+            PusdEvent::CancelWithdraw { .. } => "cancel_withdraw",
+            PusdEvent::RefundExpired { .. } => "refund_expired",
+            PusdEvent::ForceRefund { .. } => "force_refund",
+            PusdEvent::UpdateComplianceList { .. } => "update_compliance_list",
+            PusdEvent::SetPaloma { .. } => "set_paloma",
+            PusdEvent::UpdateCompass { .. } => "update_compass",
+            PusdEvent::UpdateRefundWallet { .. } => "update_refund_wallet",
+            PusdEvent::UpdateRedemptionFee { .. } => "update_redemption_fee",
+        }
+    }
+
+    fn attributes(self) -> Vec<(&'static str, String)> {
+        let mut attributes = vec![];
+        match self {
+            PusdEvent::Instantiate {
+                owner,
+                minter,
+                denom,
+                decimals,
+                retry_delay,
+            } => {
+                attributes.push(("owner", owner.to_string()));
+                attributes.push(("minter", minter.to_string()));
+                attributes.push(("denom", denom));
+                attributes.push(("decimals", decimals.to_string()));
+                attributes.push(("retry_delay", retry_delay.to_string()));
+            }
+            PusdEvent::ImportState {
+                version,
+                owner,
+                chain_count,
+                entry_count,
+                expected_entries,
+            } => {
+                attributes.push(("version", version.to_string()));
+                attributes.push(("owner", owner.to_string()));
+                attributes.push(("chain_count", chain_count.to_string()));
+                attributes.push(("entry_count", entry_count.to_string()));
+                attributes.push(("expected_entries", expected_entries.to_string()));
+            }
+            PusdEvent::ImportEntries { count } => {
+                attributes.push(("count", count.to_string()));
+            }
+            PusdEvent::FinalizeImport {
+                entry_count,
+                withdrawal_count,
+                total_escrowed,
+            } => {
+                attributes.push(("entry_count", entry_count.to_string()));
+                attributes.push(("withdrawal_count", withdrawal_count.to_string()));
+                attributes.push(("total_escrowed", total_escrowed.to_string()));
+            }
+            PusdEvent::Migrate {
+                from_version,
+                version,
+                steps,
+                minter,
+            } => {
+                attributes.push(("from_version", from_version));
+                attributes.push(("version", version));
+                attributes.push(("steps", steps.join(",")));
+                if let Some(minter) = minter {
+                    attributes.push(("minter", minter.to_string()));
+                }
+            }
+            PusdEvent::RegisterChain {
+                chain_id,
+                job_id,
+                admin_job_id,
+                minimum_amount,
+                decimals,
+            }
+            | PusdEvent::UpdateChain {
+                chain_id,
+                job_id,
+                admin_job_id,
+                minimum_amount,
+                decimals,
+            } => {
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("job_id", job_id));
+                if let Some(admin_job_id) = admin_job_id {
+                    attributes.push(("admin_job_id", admin_job_id));
+                }
+                attributes.push(("minimum_amount", minimum_amount.to_string()));
+                if let Some(decimals) = decimals {
+                    attributes.push(("decimals", decimals.to_string()));
+                }
+            }
+            PusdEvent::DeregisterChain {
+                chain_id,
+                pending_count,
+            } => {
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("pending_count", pending_count.to_string()));
+            }
+            PusdEvent::SetBridge {
+                chain_reference_id,
+                erc20_address,
+                denom,
+            } => {
+                attributes.push(("chain_reference_id", chain_reference_id));
+                attributes.push(("erc20_address", erc20_address));
+                attributes.push(("denom", denom));
+            }
+            PusdEvent::UpdateConfig {
+                retry_delay,
+                owner,
+                timelock_delay,
+            } => {
+                if let Some(retry_delay) = retry_delay {
+                    attributes.push(("retry_delay", retry_delay.to_string()));
+                }
+                if let Some(owner) = owner {
+                    attributes.push(("owner", owner.to_string()));
+                }
+                if let Some(timelock_delay) = timelock_delay {
+                    attributes.push(("timelock_delay", timelock_delay.to_string()));
+                }
+            }
+            PusdEvent::UpdateOwner { owner } => {
+                attributes.push(("owner", owner.to_string()));
+            }
+            PusdEvent::UpdateTimelockDelay { timelock_delay } => {
+                attributes.push(("timelock_delay", timelock_delay.to_string()));
+            }
+            PusdEvent::ProposeAdminAction {
+                id,
+                admin_action,
+                eta,
+            } => {
+                attributes.push(("id", id.to_string()));
+                attributes.push(("admin_action", admin_action));
+                attributes.push(("eta", eta.to_string()));
+            }
+            PusdEvent::ExecuteAdminAction { id, admin_action } => {
+                attributes.push(("id", id.to_string()));
+                attributes.push(("admin_action", admin_action));
+            }
+            PusdEvent::CancelAdminAction { id } | PusdEvent::CancelMint { id } => {
+                attributes.push(("id", id.to_string()));
+            }
+            PusdEvent::UpdateMintApprovalConfig {
+                enabled,
+                threshold,
+                minimum_amount,
+            } => {
+                attributes.push(("enabled", enabled.to_string()));
+                if let Some(threshold) = threshold {
+                    attributes.push(("threshold", threshold.to_string()));
+                }
+                if let Some(minimum_amount) = minimum_amount {
+                    attributes.push(("minimum_amount", minimum_amount.to_string()));
+                }
+            }
+            PusdEvent::ProposeMint {
+                id,
+                proposer,
+                recipient,
+                amount,
+                executed,
+            } => {
+                attributes.push(("id", id.to_string()));
+                attributes.push(("proposer", proposer.to_string()));
+                attributes.push(("recipient", recipient.to_string()));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("executed", executed.to_string()));
+            }
+            PusdEvent::ApproveMint {
+                id,
+                approver,
+                approvals,
+                executed,
+            } => {
+                attributes.push(("id", id.to_string()));
+                attributes.push(("approver", approver.to_string()));
+                attributes.push(("approvals", approvals.to_string()));
+                attributes.push(("executed", executed.to_string()));
+            }
+            PusdEvent::Mint {
+                recipient,
+                amount,
+                denom,
+            } => {
+                attributes.push(("recipient", recipient.to_string()));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("denom", denom));
+            }
+            PusdEvent::Unmint {
+                redemption_id,
+                minter,
+                amount,
+                denom,
+                burned,
+                reference,
+            } => {
+                attributes.push(("redemption_id", redemption_id.to_string()));
+                attributes.push(("minter", minter.to_string()));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("denom", denom));
+                attributes.push(("burned", burned.to_string()));
+                if let Some(reference) = reference {
+                    attributes.push(("reference", reference));
+                }
+            }
+            PusdEvent::Withdraw {
+                nonce,
+                chain_id,
+                burner,
+                recipient,
+                amount,
+                remote_amount,
+                dust,
+                denom,
+                transport,
+                operator,
+                refund_address,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("burner", burner.to_string()));
+                attributes.push(("recipient", recipient));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("remote_amount", remote_amount.to_string()));
+                attributes.push(("dust", dust.to_string()));
+                attributes.push(("denom", denom));
+                attributes.push(("transport", transport.name().to_string()));
+                if let Some(operator) = operator {
+                    attributes.push(("operator", operator.to_string()));
+                }
+                if let Some(refund_address) = refund_address {
+                    attributes.push(("refund_address", refund_address.to_string()));
+                }
+            }
+            PusdEvent::SkywaySend {
+                nonce,
+                chain_id,
+                transaction_id,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("transaction_id", transaction_id.to_string()));
+            }
+            PusdEvent::GrantAllowance {
+                owner,
+                spender,
+                chain_id,
+                amount,
+            } => {
+                attributes.push(("owner", owner.to_string()));
+                attributes.push(("spender", spender.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("amount", amount.to_string()));
+            }
+            PusdEvent::RevokeAllowance {
+                owner,
+                spender,
+                chain_id,
+            } => {
+                attributes.push(("owner", owner.to_string()));
+                attributes.push(("spender", spender.to_string()));
+                attributes.push(("chain_id", chain_id));
+            }
+            PusdEvent::ReWithdraw {
+                nonce,
+                chain_id,
+                burner,
+                recipient,
+                remote_amount,
+                attempt,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("burner", burner.to_string()));
+                attributes.push(("recipient", recipient));
+                attributes.push(("remote_amount", remote_amount.to_string()));
+                attributes.push(("attempt", attempt.to_string()));
+            }
+            PusdEvent::NeedsReview {
+                nonce,
+                chain_id,
+                attempts,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("attempts", attempts.to_string()));
+            }
+            PusdEvent::RetryBatch {
+                chain_id,
+                nonces,
+                keeper,
+                reward,
+                denom,
+                next_start_after,
+            } => {
+                let nonces: Vec<String> = nonces.iter().map(|nonce| nonce.to_string()).collect();
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("nonces", nonces.join(",")));
+                attributes.push(("keeper", keeper.to_string()));
+                attributes.push(("reward", reward.to_string()));
+                attributes.push(("denom", denom));
+                if let Some(next_start_after) = next_start_after {
+                    attributes.push(("next_start_after", next_start_after.to_string()));
+                }
+            }
+            PusdEvent::FundKeeperPool {
+                sender,
+                amount,
+                keeper_pool,
+            } => {
+                attributes.push(("sender", sender.to_string()));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("keeper_pool", keeper_pool.to_string()));
+            }
+            PusdEvent::ResetRetries { nonce, chain_id } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+            }
+            PusdEvent::Burn {
+                nonce,
+                chain_id,
+                burner,
+                amount,
+                denom,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("burner", burner.to_string()));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("denom", denom));
+            }
+            PusdEvent::CancelWithdraw {
+                nonce,
+                chain_id,
+                burner,
+                recipient,
+                amount,
+                denom,
+                refund_address,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("burner", burner.to_string()));
+                attributes.push(("recipient", recipient));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("denom", denom));
+                attributes.push(("refund_address", refund_address.to_string()));
+            }
+            PusdEvent::RefundExpired {
+                chain_id,
+                nonces,
+                amount,
+                denom,
+                sender,
+                next_start_after,
+            } => {
+                let nonces: Vec<String> = nonces.iter().map(|nonce| nonce.to_string()).collect();
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("nonces", nonces.join(",")));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("denom", denom));
+                attributes.push(("sender", sender.to_string()));
+                if let Some(next_start_after) = next_start_after {
+                    attributes.push(("next_start_after", next_start_after.to_string()));
+                }
+            }
+            PusdEvent::ForceRefund {
+                nonce,
+                chain_id,
+                burner,
+                amount,
+                denom,
+                refund_address,
+                sender,
+            } => {
+                attributes.push(("nonce", nonce.to_string()));
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("burner", burner.to_string()));
+                attributes.push(("amount", amount.to_string()));
+                attributes.push(("denom", denom));
+                attributes.push(("refund_address", refund_address.to_string()));
+                attributes.push(("sender", sender.to_string()));
+            }
+            PusdEvent::UpdateComplianceList {
+                list,
+                added,
+                removed,
+            } => {
+                attributes.push(("list", list));
+                attributes.push(("added", added.join(",")));
+                attributes.push(("removed", removed.join(",")));
+            }
+            PusdEvent::SetPaloma { chain_id } => {
+                attributes.push(("chain_id", chain_id));
+            }
+            PusdEvent::UpdateCompass {
+                chain_id,
+                new_compass,
+            } => {
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("new_compass", new_compass));
+            }
+            PusdEvent::UpdateRefundWallet {
+                chain_id,
+                new_refund_wallet,
+            } => {
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("new_refund_wallet", new_refund_wallet));
+            }
+            PusdEvent::UpdateRedemptionFee {
+                chain_id,
+                new_redemption_fee,
+            } => {
+                attributes.push(("chain_id", chain_id));
+                attributes.push(("new_redemption_fee", new_redemption_fee.to_string()));
+            }
+        }
+        attributes
+    }
+}
+
+impl From<PusdEvent> for Event {
+    fn from(event: PusdEvent) -> Self {
+        Event::new(format!("pusd_{}", event.action())).add_attributes(event.attributes())
+    }
+}
+
+/// Response carrying the `action` attribute and the typed event of `event`.
+pub fn response<T>(event: PusdEvent) -> Response<T> {
+    Response::new()
+        .add_attribute("action", event.action())
+        .add_event(event)
+}
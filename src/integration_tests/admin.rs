@@ -0,0 +1,381 @@
+use cosmwasm_std::{to_json_string, Binary, Uint128};
+use ethabi::ParamType;
+
+use super::*;
+use crate::msg::{BridgeResponse, ExecuteJob, ExecuteMsg, QueryMsg};
+use crate::state::{
+    AdminAction, ChainSettingAction, ChainSettingChange, JobHints, QueuedAdminAction, RemoteConfig,
+};
+
+const COMPASS: &str = "0x00000000000000000000000000000000000000c0";
+
+#[test]
+fn set_bridge_records_mapping_and_applies_skyway_msg() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+
+    for erc20_address in [RECIPIENT, COMPASS] {
+        suite.execute(
+            &owner,
+            &ExecuteMsg::SetBridge {
+                chain_reference_id: CHAIN_ID.to_string(),
+                erc20_address: erc20_address.to_string(),
+            },
+            &[],
+        );
+    }
+
+    let bridge: BridgeResponse = suite.query(&QueryMsg::GetBridge {
+        chain_reference_id: CHAIN_ID.to_string(),
+    });
+    assert_eq!(bridge.bridge.erc20_address, COMPASS);
+    assert_eq!(bridge.history.len(), 2);
+    assert_eq!(bridge.history[0].erc20_address, RECIPIENT);
+    let mapping = suite.erc20_to_denom(CHAIN_ID).unwrap();
+    assert_eq!(mapping.erc20_address, COMPASS);
+    assert_eq!(mapping.token_denom, DENOM);
+}
+
+#[test]
+#[should_panic(expected = "Chain not registered")]
+fn set_bridge_for_unregistered_chain_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::SetBridge {
+            chain_reference_id: "unknown".to_string(),
+            erc20_address: RECIPIENT.to_string(),
+        },
+        &[],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Chain already registered")]
+fn register_chain_twice_fails() {
+    let mut suite = setup();
+    suite.register_chain(CHAIN_ID, chain_setting());
+}
+
+#[test]
+#[should_panic(expected = "Job not found")]
+fn register_chain_with_unknown_job_fails() {
+    let mut suite = setup();
+    suite.register_chain(
+        "arbitrum",
+        ChainSetting {
+            job_id: "unknown_job".to_string(),
+            ..chain_setting()
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Job not found")]
+fn admin_call_to_removed_job_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.create_job("admin_job");
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                admin_job_id: Some("admin_job".to_string()),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite.remove_job("admin_job");
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::SetPaloma {
+            chain_id: CHAIN_ID.to_string(),
+        },
+        &[],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Pending withdrawals exist for chain")]
+fn deregister_chain_with_pending_withdrawals_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.withdraw(&user, 5_000);
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::DeregisterChain {
+            chain_id: CHAIN_ID.to_string(),
+            force: None,
+        },
+        &[],
+    );
+}
+
+#[test]
+fn forced_deregister_chain_is_recorded_in_history() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.withdraw(&user, 5_000);
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::DeregisterChain {
+            chain_id: CHAIN_ID.to_string(),
+            force: Some(true),
+        },
+        &[],
+    );
+
+    let history: Vec<ChainSettingChange> = suite.query(&QueryMsg::GetChainSettingHistory {
+        chain_id: CHAIN_ID.to_string(),
+    });
+    let actions: Vec<ChainSettingAction> = history.into_iter().map(|c| c.action).collect();
+    assert_eq!(
+        actions,
+        vec![ChainSettingAction::Register, ChainSettingAction::Deregister]
+    );
+}
+
+#[test]
+fn update_compass_dispatches_job_and_records_remote_config() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateCompass {
+            chain_id: CHAIN_ID.to_string(),
+            new_compass: COMPASS.to_string(),
+        },
+        &[],
+    );
+
+    let jobs = suite.jobs();
+    assert_eq!(
+        decode_call("update_compass", &[ParamType::Address], &jobs[0].payload),
+        vec![address(COMPASS)]
+    );
+    let remote_config: RemoteConfig = suite.query(&QueryMsg::GetRemoteConfig {
+        chain_id: CHAIN_ID.to_string(),
+    });
+    assert_eq!(remote_config.compass.as_deref(), Some(COMPASS));
+    assert!(!remote_config.paloma_set);
+}
+
+#[test]
+fn admin_calls_use_admin_job_and_hints() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    suite.create_job("admin_job");
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                admin_job_id: Some("admin_job".to_string()),
+                job_hints: Some(JobHints {
+                    gas_limit: Some(500_000),
+                    priority: None,
+                }),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::SetPaloma {
+            chain_id: CHAIN_ID.to_string(),
+        },
+        &[],
+    );
+    suite.withdraw(&user, 5_000);
+
+    let jobs = suite.jobs();
+    assert_eq!(jobs[0].job_id, "admin_job");
+    assert_eq!(jobs[1].job_id, JOB_ID);
+    assert_eq!(jobs[1].gas_limit, Some(500_000));
+    assert_eq!(jobs[1].priority, None);
+}
+
+#[test]
+fn unset_job_hints_are_left_out_of_the_message() {
+    let job = ExecuteJob {
+        job_id: JOB_ID.to_string(),
+        payload: Binary::default(),
+        gas_limit: None,
+        priority: None,
+    };
+
+    assert_eq!(
+        to_json_string(&job).unwrap(),
+        format!(r#"{{"job_id":"{JOB_ID}","payload":""}}"#)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Admin job ID must differ from job ID")]
+fn admin_job_shared_with_withdraw_job_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                admin_job_id: Some(JOB_ID.to_string()),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+}
+
+#[test]
+fn timelocked_action_executes_after_delay() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateConfig {
+            retry_delay: None,
+            owner: None,
+            timelock_delay: Some(86_400),
+        },
+        &[],
+    );
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ProposeAdminAction {
+            action: AdminAction::UpdateRedemptionFee {
+                chain_id: CHAIN_ID.to_string(),
+                new_redemption_fee: Uint128::new(25),
+            },
+        },
+        &[],
+    );
+    let queued: Vec<(u64, QueuedAdminAction)> = suite.query(&QueryMsg::GetQueuedAdminActions {});
+    assert_eq!(queued.len(), 1);
+    assert!(suite.jobs().is_empty());
+
+    suite.advance(86_400);
+    suite.execute(&owner, &ExecuteMsg::ExecuteAdminAction { id: 1 }, &[]);
+
+    assert_eq!(
+        decode_call(
+            "update_redemption_fee",
+            &[ParamType::Uint(256)],
+            &suite.jobs()[0].payload
+        ),
+        vec![uint(25)]
+    );
+    let queued: Vec<(u64, QueuedAdminAction)> = suite.query(&QueryMsg::GetQueuedAdminActions {});
+    assert!(queued.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Timelock not expired")]
+fn queued_action_before_delay_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateConfig {
+            retry_delay: None,
+            owner: None,
+            timelock_delay: Some(86_400),
+        },
+        &[],
+    );
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ProposeAdminAction {
+            action: AdminAction::UpdateOwner {
+                owner: suite.user.clone(),
+            },
+        },
+        &[],
+    );
+
+    suite.advance(86_399);
+    suite.execute(&owner, &ExecuteMsg::ExecuteAdminAction { id: 1 }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Timelocked, use ProposeAdminAction")]
+fn direct_admin_message_is_refused_while_timelocked() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateConfig {
+            retry_delay: None,
+            owner: None,
+            timelock_delay: Some(86_400),
+        },
+        &[],
+    );
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateRefundWallet {
+            chain_id: CHAIN_ID.to_string(),
+            new_refund_wallet: RECIPIENT.to_string(),
+        },
+        &[],
+    );
+}
+
+fn propose_timelocked(suite: &mut Suite, action: AdminAction) {
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateConfig {
+            retry_delay: None,
+            owner: None,
+            timelock_delay: Some(86_400),
+        },
+        &[],
+    );
+    suite.execute(&owner, &ExecuteMsg::ProposeAdminAction { action }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid compass address")]
+fn proposing_invalid_compass_fails() {
+    let mut suite = setup();
+    propose_timelocked(
+        &mut suite,
+        AdminAction::UpdateCompass {
+            chain_id: CHAIN_ID.to_string(),
+            new_compass: "not-an-address".to_string(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Keeper reward requires a retry policy")]
+fn proposing_invalid_chain_setting_fails() {
+    let mut suite = setup();
+    propose_timelocked(
+        &mut suite,
+        AdminAction::RegisterChain {
+            chain_id: "arbitrum".to_string(),
+            chain_setting: ChainSetting {
+                keeper_reward: Some(Uint128::new(10)),
+                ..chain_setting()
+            },
+        },
+    );
+}
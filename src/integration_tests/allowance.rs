@@ -0,0 +1,169 @@
+use cosmwasm_std::{coins, Uint128};
+use ethabi::Token;
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::BurnInfo;
+
+const ALLOWANCE: u128 = 10_000;
+
+/// Funds a custody operator and grants it an allowance from the user.
+fn setup_operator(suite: &mut Suite) -> Addr {
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    let operator = suite.app.api().addr_make("custody");
+    suite
+        .app
+        .send_tokens(owner, operator.clone(), &coins(USER_BALANCE / 2, DENOM))
+        .unwrap();
+    suite.execute(
+        &user,
+        &ExecuteMsg::GrantAllowance {
+            spender: operator.to_string(),
+            chain_id: CHAIN_ID.to_string(),
+            amount: Uint128::new(ALLOWANCE),
+        },
+        &[],
+    );
+    operator
+}
+
+fn withdraw_on_behalf(suite: &mut Suite, operator: &Addr, amount: u128) {
+    let user = suite.user.clone();
+    suite.execute(
+        operator,
+        &ExecuteMsg::Withdraw {
+            chain_id: CHAIN_ID.to_string(),
+            recipient: RECIPIENT.to_string(),
+            on_behalf_of: Some(user.to_string()),
+            refund_address: None,
+        },
+        &coins(amount, DENOM),
+    );
+}
+
+#[test]
+fn operator_withdraws_on_behalf_of_user() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let operator = setup_operator(&mut suite);
+
+    withdraw_on_behalf(&mut suite, &operator, 4_000);
+
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce: 1 });
+    assert_eq!(burn_info.burner, user);
+    assert_eq!(burn_info.operator, Some(operator.clone()));
+    assert_eq!(
+        decode_withdraw(&suite.jobs()[0].payload)[0],
+        Token::FixedBytes(suite.canonical(&user))
+    );
+    let allowance: Uint128 = suite.query(&QueryMsg::GetAllowance {
+        owner: user.to_string(),
+        spender: operator.to_string(),
+        chain_id: CHAIN_ID.to_string(),
+    });
+    assert_eq!(allowance, Uint128::new(ALLOWANCE - 4_000));
+    assert_eq!(suite.balance(&user), USER_BALANCE);
+}
+
+#[test]
+#[should_panic(expected = "Allowance exceeded")]
+fn withdraw_above_allowance_fails() {
+    let mut suite = setup();
+    let operator = setup_operator(&mut suite);
+
+    withdraw_on_behalf(&mut suite, &operator, ALLOWANCE + 1);
+}
+
+#[test]
+#[should_panic(expected = "Allowance exceeded")]
+fn withdraw_after_revoke_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let operator = setup_operator(&mut suite);
+    suite.execute(
+        &user,
+        &ExecuteMsg::RevokeAllowance {
+            spender: operator.to_string(),
+            chain_id: CHAIN_ID.to_string(),
+        },
+        &[],
+    );
+
+    withdraw_on_behalf(&mut suite, &operator, 4_000);
+}
+
+#[test]
+fn operator_cancel_refunds_user() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let operator = setup_operator(&mut suite);
+    withdraw_on_behalf(&mut suite, &operator, 4_000);
+    let operator_balance = suite.balance(&operator);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&operator, &ExecuteMsg::CancelWithdraw { nonce: 1 }, &[]);
+
+    assert_eq!(suite.balance(&user), USER_BALANCE + 4_000);
+    assert_eq!(suite.balance(&operator), operator_balance);
+}
+
+#[test]
+fn dust_of_operator_withdraw_is_refunded_to_user() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let operator = setup_operator(&mut suite);
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                decimals: Some(4),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    let operator_balance = suite.balance(&operator);
+
+    withdraw_on_behalf(&mut suite, &operator, 4_055);
+
+    assert_eq!(suite.balance(&user), USER_BALANCE + 55);
+    assert_eq!(suite.balance(&operator), operator_balance - 4_055);
+}
+
+#[test]
+fn re_withdraw_by_other_account_encodes_burner() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&owner, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    let jobs = suite.jobs();
+    assert_eq!(
+        decode_withdraw(&jobs[1].payload)[0],
+        Token::FixedBytes(suite.canonical(&user))
+    );
+}
+
+#[test]
+#[should_panic(expected = "address not normalized")]
+fn grant_allowance_to_unnormalized_spender_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let operator = suite.app.api().addr_make("custody");
+
+    suite.execute(
+        &user,
+        &ExecuteMsg::GrantAllowance {
+            spender: operator.to_string().to_uppercase(),
+            chain_id: CHAIN_ID.to_string(),
+            amount: Uint128::new(ALLOWANCE),
+        },
+        &[],
+    );
+}
@@ -0,0 +1,164 @@
+use cosmwasm_std::Uint128;
+
+use super::*;
+use crate::msg::{ComplianceStatusResponse, ExecuteMsg, QueryMsg};
+use crate::state::ComplianceList;
+
+fn update_list(suite: &mut Suite, list: ComplianceList, add: Vec<String>, remove: Vec<String>) {
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateComplianceList { list, add, remove },
+        &[],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Account is denylisted")]
+fn withdraw_from_denylisted_sender_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    update_list(
+        &mut suite,
+        ComplianceList::SenderDenylist,
+        vec![user.to_string()],
+        vec![],
+    );
+
+    suite.withdraw(&user, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "Recipient is denylisted")]
+fn withdraw_to_denylisted_recipient_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    // Recipients are matched case insensitively
+    update_list(
+        &mut suite,
+        ComplianceList::RecipientDenylist,
+        vec![RECIPIENT.to_uppercase().replace("0X", "0x")],
+        vec![],
+    );
+
+    suite.withdraw(&user, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "Account is not allowlisted")]
+fn withdraw_to_allowlist_only_chain_fails_for_unlisted_sender() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                allowlist_only: true,
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+
+    suite.withdraw(&user, 5_000);
+}
+
+#[test]
+fn allowlisted_sender_withdraws_to_allowlist_only_chain() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                allowlist_only: true,
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    update_list(
+        &mut suite,
+        ComplianceList::Allowlist,
+        vec![user.to_string()],
+        vec![],
+    );
+
+    assert_eq!(suite.withdraw(&user, 5_000), 1);
+    let allowlist: Vec<String> = suite.query(&QueryMsg::GetComplianceList {
+        list: ComplianceList::Allowlist,
+    });
+    assert_eq!(allowlist, vec![user.to_string()]);
+}
+
+#[test]
+#[should_panic(expected = "Account is denylisted")]
+fn mint_pusd_to_denylisted_account_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    update_list(
+        &mut suite,
+        ComplianceList::SenderDenylist,
+        vec![user.to_string()],
+        vec![],
+    );
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::MintPusd {
+            recipient: user,
+            amount: Uint128::new(7_000),
+        },
+        &[],
+    );
+}
+
+#[test]
+fn removed_members_are_no_longer_screened() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    update_list(
+        &mut suite,
+        ComplianceList::SenderDenylist,
+        vec![user.to_string()],
+        vec![],
+    );
+    update_list(
+        &mut suite,
+        ComplianceList::RecipientDenylist,
+        vec![RECIPIENT.to_string()],
+        vec![],
+    );
+    let status: ComplianceStatusResponse = suite.query(&QueryMsg::ComplianceStatus {
+        sender: Some(user.to_string()),
+        recipient: Some(RECIPIENT.to_string()),
+    });
+    assert!(status.sender_denylisted);
+    assert!(status.recipient_denylisted);
+
+    update_list(
+        &mut suite,
+        ComplianceList::SenderDenylist,
+        vec![],
+        vec![user.to_string()],
+    );
+    update_list(
+        &mut suite,
+        ComplianceList::RecipientDenylist,
+        vec![],
+        vec![RECIPIENT.to_string()],
+    );
+
+    assert_eq!(suite.withdraw(&user, 5_000), 1);
+    let status: ComplianceStatusResponse = suite.query(&QueryMsg::ComplianceStatus {
+        sender: Some(user.to_string()),
+        recipient: Some(RECIPIENT.to_string()),
+    });
+    assert!(!status.sender_denylisted);
+    assert!(!status.recipient_denylisted);
+}
@@ -0,0 +1,130 @@
+use cosmwasm_std::Uint128;
+
+use super::*;
+use crate::msg::{ExecuteMsg, ExpiredWithdrawalsResponse, QueryMsg, SolvencyResponse};
+use crate::state::{BurnInfo, ExpiredWithdrawal};
+
+const EXPIRY_WINDOW: u64 = 86_400;
+
+fn setup_expiry() -> Suite {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                expiry_window: Some(EXPIRY_WINDOW),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite
+}
+
+fn expired_nonces(suite: &Suite) -> Vec<u64> {
+    let expired: ExpiredWithdrawalsResponse = suite.query(&QueryMsg::GetExpiredWithdrawals {
+        chain_id: CHAIN_ID.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    expired.nonces
+}
+
+fn refund_expired(suite: &mut Suite, limit: Option<u32>) {
+    let keeper = suite.app.api().addr_make("keeper");
+    suite.execute(
+        &keeper,
+        &ExecuteMsg::RefundExpired {
+            chain_id: CHAIN_ID.to_string(),
+            start_after: None,
+            limit,
+        },
+        &[],
+    );
+}
+
+#[test]
+fn keeper_refunds_expired_withdrawals_in_batches() {
+    let mut suite = setup_expiry();
+    let user = suite.user.clone();
+    for _ in 0..3 {
+        suite.withdraw(&user, 5_000);
+    }
+
+    suite.advance(EXPIRY_WINDOW);
+    assert_eq!(expired_nonces(&suite), vec![1, 2, 3]);
+    refund_expired(&mut suite, Some(2));
+
+    assert_eq!(expired_nonces(&suite), vec![3]);
+    assert_eq!(suite.balance(&user), USER_BALANCE - 5_000);
+    let expired: Option<ExpiredWithdrawal> =
+        suite.query(&QueryMsg::GetExpiredWithdrawal { nonce: 1 });
+    assert_eq!(expired.unwrap().refunded_at, suite.app.block_info().time);
+
+    refund_expired(&mut suite, None);
+
+    assert!(expired_nonces(&suite).is_empty());
+    assert_eq!(suite.balance(&user), USER_BALANCE);
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_escrowed, Uint128::zero());
+}
+
+#[test]
+fn withdrawals_within_window_are_kept() {
+    let mut suite = setup_expiry();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(EXPIRY_WINDOW - 1);
+    refund_expired(&mut suite, None);
+
+    assert!(expired_nonces(&suite).is_empty());
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.amount, 5_000);
+}
+
+#[test]
+fn retried_withdrawal_is_refunded_once_no_longer_in_flight() {
+    let mut suite = setup_expiry();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(EXPIRY_WINDOW - 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+    suite.advance(1);
+    refund_expired(&mut suite, None);
+
+    assert!(expired_nonces(&suite).is_empty());
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.attempts, 1);
+
+    // Retries don't extend the window past the retry delay
+    suite.advance(RETRY_DELAY);
+    assert_eq!(expired_nonces(&suite), vec![nonce]);
+}
+
+#[test]
+fn zero_limit_refunds_one_withdrawal() {
+    let mut suite = setup_expiry();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 5_000);
+    suite.withdraw(&user, 5_000);
+
+    suite.advance(EXPIRY_WINDOW);
+    refund_expired(&mut suite, Some(0));
+
+    assert_eq!(expired_nonces(&suite), vec![2]);
+}
+
+#[test]
+#[should_panic(expected = "Withdraw expired")]
+fn re_withdraw_after_expiry_fails() {
+    let mut suite = setup_expiry();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(EXPIRY_WINDOW);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+}
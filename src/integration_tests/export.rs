@@ -0,0 +1,226 @@
+use cosmwasm_std::{coin, Uint128};
+
+use super::*;
+use crate::msg::{ExecuteMsg, ExportCursor, ExportEntry, ExportPage, QueryMsg, StateExport};
+use crate::state::{BurnInfo, ComplianceList, Redemption, State};
+
+const ESCROW: u128 = 3 * 5_000;
+
+fn export_first_page(suite: &Suite) -> StateExport {
+    suite.query(&QueryMsg::ExportState { limit: Some(2) })
+}
+
+fn export_page(suite: &Suite, start_after: ExportCursor) -> ExportPage {
+    suite.query(&QueryMsg::ExportEntries {
+        start_after,
+        limit: Some(2),
+    })
+}
+
+// Instantiates a new contract from the first export page, funded for the escrow
+fn import(suite: &mut Suite, export: StateExport) {
+    let owner = suite.owner.clone();
+    suite.contract = suite
+        .app
+        .instantiate_contract(
+            suite.code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                retry_delay: 0,
+                minter: owner.clone(),
+                denom: String::new(),
+                decimals: None,
+                timelock_delay: None,
+                import: Some(export),
+            },
+            &[coin(ESCROW, DENOM)],
+            "pusd-manager",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+}
+
+fn setup_export() -> (Suite, Vec<(u64, BurnInfo)>) {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    for _ in 0..3 {
+        suite.withdraw(&user, 5_000);
+    }
+    let withdrawals = suite.query(&QueryMsg::GetWithdrawList {});
+    (suite, withdrawals)
+}
+
+#[test]
+fn exported_state_is_restored_in_new_contract() {
+    let (mut suite, withdrawals) = setup_export();
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    let state: State = suite.query(&QueryMsg::GetState {});
+    let first = export_first_page(&suite);
+    assert_eq!(first.entry_count, 3);
+    assert_eq!(first.entries.len(), 2);
+    let second = export_page(&suite, first.next_start_after.clone().unwrap());
+    assert_eq!(second.next_start_after, None);
+
+    import(&mut suite, first);
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ImportEntries {
+            entries: second.entries,
+        },
+        &[],
+    );
+    suite.execute(&owner, &ExecuteMsg::FinalizeImport {}, &[]);
+
+    let imported: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    assert_eq!(imported, withdrawals);
+    let imported_state: State = suite.query(&QueryMsg::GetState {});
+    assert_eq!(imported_state, state);
+    let nonce = suite.withdraw(&user, 5_000);
+    assert_eq!(nonce, state.last_nonce + 1);
+}
+
+#[test]
+#[should_panic(expected = "Import in progress")]
+fn execute_during_import_fails() {
+    let (mut suite, _) = setup_export();
+    let user = suite.user.clone();
+    let first = export_first_page(&suite);
+    import(&mut suite, first);
+
+    suite.withdraw(&user, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "Import incomplete")]
+fn finalize_with_missing_entries_fails() {
+    let (mut suite, _) = setup_export();
+    let owner = suite.owner.clone();
+    let first = export_first_page(&suite);
+    import(&mut suite, first);
+
+    suite.execute(&owner, &ExecuteMsg::FinalizeImport {}, &[]);
+}
+
+#[test]
+fn compliance_lists_and_redemptions_are_restored() {
+    let (mut suite, _) = setup_export();
+    let owner = suite.owner.clone();
+    let minter = suite.minter.clone();
+    let user = suite.user.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateComplianceList {
+            list: ComplianceList::SenderDenylist,
+            add: vec![user.to_string()],
+            remove: vec![],
+        },
+        &[],
+    );
+    suite.execute(
+        &minter,
+        &ExecuteMsg::UnmintPusd {
+            amount: Uint128::new(1_000),
+            reference: Some("collateral-1".to_string()),
+            burn: None,
+        },
+        &[],
+    );
+    let export: StateExport = suite.query(&QueryMsg::ExportState { limit: None });
+    assert_eq!(export.entry_count, 5);
+    assert_eq!(export.next_start_after, None);
+
+    import(&mut suite, export);
+    suite.execute(&owner, &ExecuteMsg::FinalizeImport {}, &[]);
+
+    let denylist: Vec<String> = suite.query(&QueryMsg::GetComplianceList {
+        list: ComplianceList::SenderDenylist,
+    });
+    assert_eq!(denylist, vec![user.to_string()]);
+    let by_reference: Option<(u64, Redemption)> =
+        suite.query(&QueryMsg::GetRedemptionByReference {
+            reference: "collateral-1".to_string(),
+        });
+    assert_eq!(by_reference.unwrap().0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Skyway withdrawals must be settled before export")]
+fn import_with_skyway_transfer_in_transit_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                transport: Transport::Skyway,
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite.execute(
+        &owner,
+        &ExecuteMsg::SetBridge {
+            chain_reference_id: CHAIN_ID.to_string(),
+            erc20_address: "0x0000000000000000000000000000000000000abc".to_string(),
+        },
+        &[],
+    );
+    suite.withdraw(&user, 5_000);
+    let export = export_first_page(&suite);
+
+    import(&mut suite, export);
+}
+
+#[test]
+fn zero_limit_exports_one_entry() {
+    let (suite, _) = setup_export();
+    let export: StateExport = suite.query(&QueryMsg::ExportState { limit: Some(0) });
+
+    assert!(matches!(
+        export.entries.as_slice(),
+        [ExportEntry::Withdrawal { nonce: 1, .. }]
+    ));
+    assert_eq!(export.next_start_after, Some(ExportCursor::Withdrawal(1)));
+}
+
+#[test]
+fn export_after_forced_deregister_is_restored() {
+    let (mut suite, withdrawals) = setup_export();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::SetBridge {
+            chain_reference_id: CHAIN_ID.to_string(),
+            erc20_address: "0x0000000000000000000000000000000000000abc".to_string(),
+        },
+        &[],
+    );
+    suite.execute(
+        &owner,
+        &ExecuteMsg::DeregisterChain {
+            chain_id: CHAIN_ID.to_string(),
+            force: Some(true),
+        },
+        &[],
+    );
+    let first = export_first_page(&suite);
+    assert!(first.bridges.is_empty());
+    let second = export_page(&suite, first.next_start_after.clone().unwrap());
+
+    import(&mut suite, first);
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ImportEntries {
+            entries: second.entries,
+        },
+        &[],
+    );
+    suite.execute(&owner, &ExecuteMsg::FinalizeImport {}, &[]);
+
+    let imported: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    assert_eq!(imported, withdrawals);
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use cosmwasm_std::{coin, Uint128};
+use proptest::prelude::*;
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg, SolvencyResponse};
+use crate::state::{BurnInfo, State};
+
+#[derive(Clone, Debug)]
+enum Op {
+    Withdraw { owner_is_burner: bool, amount: u128 },
+    ReWithdraw { pick: usize },
+    Cancel { pick: usize },
+    Burn { pick: usize },
+    Mint { amount: u128 },
+    Unmint { amount: u128 },
+    // Mint or unmint the whole inventory plus part of the escrow
+    Overdraw { unmint: bool, extra: u128 },
+    Advance { seconds: u64 },
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (any::<bool>(), MINIMUM_AMOUNT + 1..100_000u128).prop_map(
+            |(owner_is_burner, amount)| Op::Withdraw { owner_is_burner, amount }
+        ),
+        1 => any::<usize>().prop_map(|pick| Op::ReWithdraw { pick }),
+        1 => any::<usize>().prop_map(|pick| Op::Cancel { pick }),
+        1 => any::<usize>().prop_map(|pick| Op::Burn { pick }),
+        1 => (1..2 * INVENTORY).prop_map(|amount| Op::Mint { amount }),
+        1 => (1..2 * INVENTORY).prop_map(|amount| Op::Unmint { amount }),
+        1 => (any::<bool>(), 1..100_000u128).prop_map(|(unmint, extra)| Op::Overdraw { unmint, extra }),
+        2 => (0..2 * RETRY_DELAY).prop_map(|seconds| Op::Advance { seconds }),
+    ]
+}
+
+/// Expected contract holdings, updated alongside every executed operation.
+struct Model {
+    // PUSD held by the contract that is not escrowed for a withdrawal
+    inventory: u128,
+    pending: BTreeMap<u64, u128>,
+    last_nonce: u64,
+}
+
+impl Model {
+    fn pick(&self, pick: usize) -> Option<u64> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.pending.keys().nth(pick % self.pending.len()).copied()
+    }
+}
+
+// Retries and cancels are only possible once the retry delay has passed
+fn retry_delay_passed(suite: &Suite, nonce: u64) -> bool {
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    burn_info.timestamp.plus_seconds(RETRY_DELAY) < suite.app.block_info().time
+}
+
+// Contract asserts panic, so a refused operation either errors or panics
+fn refused(suite: &mut Suite, sender: &Addr, msg: &ExecuteMsg) -> bool {
+    let contract = suite.contract.clone();
+    let outcome = catch_unwind(AssertUnwindSafe(|| {
+        suite
+            .app
+            .execute_contract(sender.clone(), contract, msg, &[])
+    }));
+    matches!(outcome, Err(_) | Ok(Err(_)))
+}
+
+fn apply(suite: &mut Suite, model: &mut Model, op: &Op) {
+    let owner = suite.owner.clone();
+    match *op {
+        Op::Withdraw {
+            owner_is_burner,
+            amount,
+        } => {
+            let burner = if owner_is_burner {
+                owner
+            } else {
+                suite.user.clone()
+            };
+            let nonce = suite.withdraw(&burner, amount);
+            model.pending.insert(nonce, amount);
+        }
+        Op::ReWithdraw { pick } => {
+            let Some(nonce) = model.pick(pick) else {
+                return;
+            };
+            if retry_delay_passed(suite, nonce) {
+                let burner = suite
+                    .query::<BurnInfo>(&QueryMsg::GetBurnInfo { nonce })
+                    .burner;
+                suite.execute(&burner, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+            }
+        }
+        Op::Cancel { pick } => {
+            let Some(nonce) = model.pick(pick) else {
+                return;
+            };
+            if retry_delay_passed(suite, nonce) {
+                let burner = suite
+                    .query::<BurnInfo>(&QueryMsg::GetBurnInfo { nonce })
+                    .burner;
+                suite.execute(&burner, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+                model.pending.remove(&nonce);
+            }
+        }
+        Op::Burn { pick } => {
+            let Some(nonce) = model.pick(pick) else {
+                return;
+            };
+            suite.execute(&owner, &ExecuteMsg::BurnPusd { nonce }, &[]);
+            // Completed withdrawals become mint inventory
+            model.inventory += model.pending.remove(&nonce).unwrap();
+        }
+        Op::Mint { amount } => {
+            let msg = ExecuteMsg::MintPusd {
+                recipient: owner.clone(),
+                amount: Uint128::new(amount),
+            };
+            if amount <= model.inventory {
+                suite.execute(&owner, &msg, &[]);
+                model.inventory -= amount;
+            } else {
+                // Escrowed PUSD must never leave through a mint
+                assert!(refused(suite, &owner, &msg), "mint above inventory");
+            }
+        }
+        Op::Unmint { amount } => {
+            let minter = suite.minter.clone();
+            let msg = ExecuteMsg::UnmintPusd {
+                amount: Uint128::new(amount),
+                reference: None,
+                burn: None,
+            };
+            if amount <= model.inventory {
+                suite.execute(&minter, &msg, &[]);
+                model.inventory -= amount;
+            } else {
+                assert!(refused(suite, &minter, &msg), "unmint above inventory");
+            }
+        }
+        Op::Overdraw { unmint, extra } => {
+            let amount = Uint128::new(model.inventory + extra);
+            let (sender, msg) = if unmint {
+                let msg = ExecuteMsg::UnmintPusd {
+                    amount,
+                    reference: None,
+                    burn: Some(true),
+                };
+                (suite.minter.clone(), msg)
+            } else {
+                let msg = ExecuteMsg::MintPusd {
+                    recipient: owner.clone(),
+                    amount,
+                };
+                (owner, msg)
+            };
+            assert!(refused(suite, &sender, &msg), "escrow overdrawn");
+        }
+        Op::Advance { seconds } => suite.advance(seconds),
+    }
+}
+
+fn check_invariants(suite: &Suite, model: &mut Model) {
+    let withdraw_list: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    let pending: BTreeMap<u64, u128> = withdraw_list
+        .iter()
+        .map(|(nonce, burn_info)| (*nonce, burn_info.amount))
+        .collect();
+    assert_eq!(pending, model.pending, "pending withdrawals diverged");
+
+    let escrowed: u128 = pending.values().sum();
+    let balance = suite.balance(&suite.contract);
+    assert!(balance >= escrowed, "escrow not covered by balance");
+    assert_eq!(balance, model.inventory + escrowed, "balance diverged");
+
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(
+        solvency.total_escrowed.u128(),
+        escrowed,
+        "escrow counter diverged"
+    );
+    assert_eq!(solvency.mint_inventory.u128(), model.inventory);
+    assert!(solvency.solvent);
+
+    let last_nonce = suite.query::<State>(&QueryMsg::GetState {}).last_nonce;
+    assert!(last_nonce >= model.last_nonce, "nonce decreased");
+    assert!(
+        pending.keys().all(|nonce| *nonce <= last_nonce),
+        "pending nonce above last nonce"
+    );
+    model.last_nonce = last_nonce;
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn escrow_is_covered_after_any_sequence(ops in prop::collection::vec(op_strategy(), 1..40)) {
+        let mut suite = setup();
+        let mut model = Model {
+            inventory: INVENTORY,
+            pending: BTreeMap::new(),
+            last_nonce: 0,
+        };
+        for op in &ops {
+            let nonce_before = model.last_nonce;
+            apply(&mut suite, &mut model, op);
+            check_invariants(&suite, &mut model);
+            if let Op::Withdraw { .. } = op {
+                prop_assert_eq!(model.last_nonce, nonce_before + 1);
+            } else {
+                prop_assert_eq!(model.last_nonce, nonce_before);
+            }
+        }
+    }
+}
+
+#[test]
+fn withdraw_to_unknown_chain_is_not_escrowed() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let result = suite.app.execute_contract(
+        user.clone(),
+        suite.contract.clone(),
+        &ExecuteMsg::Withdraw {
+            chain_id: "unknown".to_string(),
+            recipient: RECIPIENT.to_string(),
+            on_behalf_of: None,
+            refund_address: None,
+        },
+        &[coin(5_000, DENOM)],
+    );
+
+    assert!(result.is_err());
+    assert_eq!(suite.balance(&user), USER_BALANCE);
+}
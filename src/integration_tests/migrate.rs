@@ -0,0 +1,166 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw2::{ContractVersion, CONTRACT};
+use serde::Serialize;
+
+use super::*;
+use crate::msg::{
+    ExpiredWithdrawalsResponse, MigrateMsg, QueryMsg, ReconciliationResponse, SolvencyResponse,
+};
+use crate::state::{State, CHAIN_ACCOUNTING, CHAIN_WITHDRAWALS};
+
+#[derive(Serialize)]
+struct OldState {
+    retry_delay: u64,
+    owner: Addr,
+    denom: String,
+    last_nonce: u64,
+}
+
+fn set_version(suite: &mut Suite, version: &str) {
+    suite.set_raw(
+        CONTRACT.as_slice(),
+        &ContractVersion {
+            contract: "crates.io:pusd-manager-cw".to_string(),
+            version: version.to_string(),
+        },
+    );
+}
+
+#[test]
+fn migrate_unversioned_state_adds_minter_and_changes_denom_admin() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.remove_raw(CONTRACT.as_slice());
+    suite.set_raw(
+        b"state",
+        &OldState {
+            retry_delay: RETRY_DELAY,
+            owner: owner.clone(),
+            denom: DENOM.to_string(),
+            last_nonce: 42,
+        },
+    );
+    let new_minter = suite.app.api().addr_make("new_minter");
+
+    suite
+        .migrate(&MigrateMsg {
+            minter: Some(new_minter.clone()),
+        })
+        .unwrap();
+
+    let state: State = suite.query(&QueryMsg::GetState {});
+    assert_eq!(state.minter, new_minter);
+    assert_eq!(state.last_nonce, 42);
+    assert_eq!(suite.denom_admin(DENOM), Some(new_minter.to_string()));
+    let version = cw2::query_contract_info(&suite.app.wrap(), suite.contract.clone()).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn migrate_unversioned_state_without_minter_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.remove_raw(CONTRACT.as_slice());
+    suite.set_raw(
+        b"state",
+        &OldState {
+            retry_delay: RETRY_DELAY,
+            owner,
+            denom: DENOM.to_string(),
+            last_nonce: 42,
+        },
+    );
+
+    let err = suite.migrate(&MigrateMsg { minter: None }).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Migration failed");
+}
+
+#[test]
+fn plain_code_upgrade_keeps_state_and_minter() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateConfig {
+            retry_delay: None,
+            owner: None,
+            timelock_delay: Some(3_600),
+        },
+        &[],
+    );
+
+    suite.migrate(&MigrateMsg { minter: None }).unwrap();
+
+    let state: State = suite.query(&QueryMsg::GetState {});
+    assert_eq!(state.minter, suite.minter);
+    assert_eq!(state.timelock_delay, 3_600);
+    assert_eq!(suite.denom_admin(DENOM), None);
+}
+
+#[test]
+fn migrate_backfills_escrow_counter() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 5_000);
+    suite.withdraw(&user, 7_000);
+    suite.remove_raw(b"accounting");
+    suite.remove_raw(&CHAIN_ACCOUNTING.key(CHAIN_ID.to_string()));
+    set_version(&mut suite, "0.1.0");
+
+    suite.migrate(&MigrateMsg { minter: None }).unwrap();
+
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_escrowed, Uint128::new(12_000));
+    let reconciliation: ReconciliationResponse = suite.query(&QueryMsg::Reconciliation {
+        chain_id: CHAIN_ID.to_string(),
+    });
+    assert_eq!(reconciliation.total_withdrawn, Uint128::new(12_000));
+    assert!(reconciliation.balanced);
+}
+
+#[test]
+fn migrate_indexes_pending_withdrawals_per_chain() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+    let key = CHAIN_WITHDRAWALS.key((CHAIN_ID.to_string(), nonce));
+    suite.remove_raw(&key);
+    set_version(&mut suite, "0.1.0");
+
+    suite.migrate(&MigrateMsg { minter: None }).unwrap();
+
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                expiry_window: Some(RETRY_DELAY),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite.advance(RETRY_DELAY + 1);
+    let expired: ExpiredWithdrawalsResponse = suite.query(&QueryMsg::GetExpiredWithdrawals {
+        chain_id: CHAIN_ID.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(expired.nonces, vec![nonce]);
+}
+
+#[test]
+fn migrate_refuses_downgrade() {
+    let mut suite = setup();
+    set_version(&mut suite, "99.0.0");
+
+    let err = suite.migrate(&MigrateMsg { minter: None }).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!(
+            "Cannot downgrade from 99.0.0 to {}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
@@ -0,0 +1,300 @@
+use cosmwasm_std::Uint128;
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::{ComplianceList, MintApprovalConfig, MintRequest, Redemption, RedemptionTotals};
+
+#[test]
+fn mint_pusd_sends_from_inventory() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let recipient = suite.app.api().addr_make("recipient");
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::MintPusd {
+            recipient: recipient.clone(),
+            amount: Uint128::new(7_000),
+        },
+        &[],
+    );
+
+    assert_eq!(suite.balance(&recipient), 7_000);
+    assert_eq!(suite.balance(&suite.contract), INVENTORY - 7_000);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient mint inventory")]
+fn mint_pusd_of_escrowed_funds_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 5_000);
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::MintPusd {
+            recipient: owner.clone(),
+            amount: Uint128::new(INVENTORY + 1),
+        },
+        &[],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn mint_pusd_by_non_owner_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+
+    suite.execute(
+        &user,
+        &ExecuteMsg::MintPusd {
+            recipient: user.clone(),
+            amount: Uint128::new(7_000),
+        },
+        &[],
+    );
+}
+
+fn enable_mint_approval(suite: &mut Suite) -> (Addr, Addr) {
+    let owner = suite.owner.clone();
+    let approver_a = suite.app.api().addr_make("approver_a");
+    let approver_b = suite.app.api().addr_make("approver_b");
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateMintApprovalConfig {
+            config: Some(MintApprovalConfig {
+                proposers: vec![],
+                approvers: vec![approver_a.clone(), approver_b.clone()],
+                threshold: 2,
+                minimum_amount: Uint128::new(10_000),
+                expiry: 3_600,
+            }),
+        },
+        &[],
+    );
+    (approver_a, approver_b)
+}
+
+#[test]
+#[should_panic(expected = "Mint requires approval")]
+fn mint_pusd_above_approval_threshold_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    enable_mint_approval(&mut suite);
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::MintPusd {
+            recipient: owner.clone(),
+            amount: Uint128::new(10_000),
+        },
+        &[],
+    );
+}
+
+#[test]
+fn approved_mint_request_executes_at_threshold() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let recipient = suite.app.api().addr_make("recipient");
+    let (approver_a, approver_b) = enable_mint_approval(&mut suite);
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ProposeMint {
+            recipient: recipient.clone(),
+            amount: Uint128::new(50_000),
+        },
+        &[],
+    );
+    suite.execute(&approver_a, &ExecuteMsg::ApproveMint { id: 1 }, &[]);
+    assert_eq!(suite.balance(&recipient), 0);
+    let open: Vec<(u64, MintRequest)> = suite.query(&QueryMsg::GetOpenMintRequests {});
+    assert_eq!(open.len(), 1);
+
+    suite.execute(&approver_b, &ExecuteMsg::ApproveMint { id: 1 }, &[]);
+
+    assert_eq!(suite.balance(&recipient), 50_000);
+    let mint_request: MintRequest = suite.query(&QueryMsg::GetMintRequest { id: 1 });
+    assert!(mint_request.executed);
+    let open: Vec<(u64, MintRequest)> = suite.query(&QueryMsg::GetOpenMintRequests {});
+    assert!(open.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Account is denylisted")]
+fn mint_request_to_recipient_denylisted_after_proposal_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let recipient = suite.app.api().addr_make("recipient");
+    let (approver_a, approver_b) = enable_mint_approval(&mut suite);
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ProposeMint {
+            recipient: recipient.clone(),
+            amount: Uint128::new(50_000),
+        },
+        &[],
+    );
+    suite.execute(&approver_a, &ExecuteMsg::ApproveMint { id: 1 }, &[]);
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateComplianceList {
+            list: ComplianceList::SenderDenylist,
+            add: vec![recipient.to_string()],
+            remove: vec![],
+        },
+        &[],
+    );
+
+    suite.execute(&approver_b, &ExecuteMsg::ApproveMint { id: 1 }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Mint request expired")]
+fn expired_mint_request_cannot_be_approved() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let (approver_a, _) = enable_mint_approval(&mut suite);
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::ProposeMint {
+            recipient: owner.clone(),
+            amount: Uint128::new(50_000),
+        },
+        &[],
+    );
+    suite.advance(3_600);
+    suite.execute(&approver_a, &ExecuteMsg::ApproveMint { id: 1 }, &[]);
+}
+
+#[test]
+fn unmint_pusd_sends_to_minter() {
+    let mut suite = setup();
+    let minter = suite.minter.clone();
+
+    suite.execute(
+        &minter,
+        &ExecuteMsg::UnmintPusd {
+            amount: Uint128::new(9_000),
+            reference: None,
+            burn: None,
+        },
+        &[],
+    );
+
+    assert_eq!(suite.balance(&minter), 9_000);
+    assert_eq!(suite.balance(&suite.contract), INVENTORY - 9_000);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient mint inventory")]
+fn unmint_pusd_of_escrowed_funds_fails() {
+    let mut suite = setup();
+    let minter = suite.minter.clone();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 5_000);
+
+    suite.execute(
+        &minter,
+        &ExecuteMsg::UnmintPusd {
+            amount: Uint128::new(INVENTORY + 1),
+            reference: None,
+            burn: None,
+        },
+        &[],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Insufficient mint inventory")]
+fn burning_escrowed_funds_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 5_000);
+
+    unmint(&mut suite, INVENTORY + 1, "collateral-1", true);
+}
+
+fn unmint(suite: &mut Suite, amount: u128, reference: &str, burn: bool) {
+    let minter = suite.minter.clone();
+    suite.execute(
+        &minter,
+        &ExecuteMsg::UnmintPusd {
+            amount: Uint128::new(amount),
+            reference: Some(reference.to_string()),
+            burn: Some(burn),
+        },
+        &[],
+    );
+}
+
+#[test]
+fn unmint_pusd_records_redemptions() {
+    let mut suite = setup();
+    let minter = suite.minter.clone();
+
+    unmint(&mut suite, 9_000, "collateral-1", false);
+    unmint(&mut suite, 4_000, "collateral-2", true);
+
+    assert_eq!(suite.balance(&minter), 9_000);
+    assert_eq!(suite.balance(&suite.contract), INVENTORY - 13_000);
+
+    let redemptions: Vec<(u64, Redemption)> = suite.query(&QueryMsg::GetRedemptions {
+        minter: Some(minter.to_string()),
+        start_after: Some(1),
+        limit: None,
+    });
+    assert_eq!(redemptions.len(), 1);
+    let (id, redemption) = &redemptions[0];
+    assert_eq!(*id, 2);
+    assert_eq!(redemption.amount, Uint128::new(4_000));
+    assert_eq!(redemption.reference.as_deref(), Some("collateral-2"));
+    assert!(redemption.burned);
+
+    let by_reference: Option<(u64, Redemption)> =
+        suite.query(&QueryMsg::GetRedemptionByReference {
+            reference: "collateral-1".to_string(),
+        });
+    assert_eq!(by_reference.unwrap().0, 1);
+    let totals: RedemptionTotals = suite.query(&QueryMsg::GetRedemptionTotals {
+        minter: minter.to_string(),
+    });
+    assert_eq!(
+        totals,
+        RedemptionTotals {
+            total_transferred: Uint128::new(9_000),
+            total_burned: Uint128::new(4_000),
+            count: 2,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Reference already used")]
+fn unmint_pusd_with_used_reference_fails() {
+    let mut suite = setup();
+    unmint(&mut suite, 9_000, "collateral-1", false);
+
+    unmint(&mut suite, 9_000, "collateral-1", true);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn unmint_pusd_by_non_minter_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UnmintPusd {
+            amount: Uint128::new(9_000),
+            reference: None,
+            burn: None,
+        },
+        &[],
+    );
+}
@@ -0,0 +1,499 @@
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, coins, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomMsg,
+    CustomQuery, Empty, Event, Order, Querier, StdResult, Storage, Uint128,
+};
+use cw_multi_test::error::{bail, AnyResult};
+use cw_multi_test::{
+    App, AppBuilder, AppResponse, BankKeeper, BankSudo, ContractWrapper, CosmosRouter, Executor,
+    Module, WasmKeeper,
+};
+use cw_storage_plus::{Item, Map};
+use ethabi::{ParamType, Token};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::msg::{
+    ChainInfoResponse, EvmQuery, ExecuteJob, ExecuteMsg, InstantiateMsg, Job, JobResponse,
+    Metadata, MigrateMsg, PalomaMsg, PalomaQuery, QueryMsg, SchedulerMsg, SchedulerQuery, SendTx,
+    SetErc20ToDenom, SkywayMsg, TokenFactoryMsg,
+};
+use crate::state::{ChainSetting, Transport};
+
+mod admin;
+mod allowance;
+mod compliance;
+mod expiry;
+mod export;
+mod invariants;
+mod migrate;
+mod mint;
+mod paloma;
+mod quota;
+mod retry;
+mod skyway;
+mod withdraw;
+
+pub const DENOM: &str = "factory/paloma1pusd/upusd";
+pub const RETRY_DELAY: u64 = 600;
+pub const CHAIN_ID: &str = "ethereum";
+pub const JOB_ID: &str = "withdraw_job";
+pub const MINIMUM_AMOUNT: u128 = 1_000;
+pub const RECIPIENT: &str = "0x1234567890123456789012345678901234567890";
+pub const INVENTORY: u128 = 1_000_000_000;
+pub const USER_BALANCE: u128 = 1_000_000_000;
+
+// Effects of PalomaMsg, recorded in the app storage so they follow transaction rollbacks
+const JOBS: Item<Vec<ExecuteJob>> = Item::new("paloma_module_jobs");
+const ERC20_TO_DENOM: Map<String, SetErc20ToDenom> = Map::new("paloma_module_erc20_to_denom");
+const DENOM_ADMINS: Map<String, String> = Map::new("paloma_module_denom_admins");
+const DENOM_METADATA: Map<String, Metadata> = Map::new("paloma_module_denom_metadata");
+const CREATED_JOBS: Map<String, Job> = Map::new("paloma_module_created_jobs");
+// Whether a chain known to Paloma is active
+const CHAIN_STATUS: Map<String, bool> = Map::new("paloma_module_chain_status");
+const LAST_SKYWAY_TX_ID: Item<u64> = Item::new("paloma_module_last_skyway_tx_id");
+// Outgoing transfers not relayed yet, with their sender
+const SKYWAY_TXS: Map<u64, (Addr, SendTx)> = Map::new("paloma_module_skyway_txs");
+
+/// Mock of the Paloma custom module: records scheduler jobs, applies Skyway
+/// and token factory messages and answers job and chain status queries.
+pub struct PalomaModule;
+
+impl Module for PalomaModule {
+    type ExecT = PalomaMsg;
+    type QueryT = PalomaQuery;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg {
+            PalomaMsg::SchedulerMsg(SchedulerMsg::ExecuteJob(execute_job)) => {
+                let mut jobs = JOBS.may_load(storage)?.unwrap_or_default();
+                jobs.push(execute_job);
+                JOBS.save(storage, &jobs)?;
+            }
+            PalomaMsg::SchedulerMsg(SchedulerMsg::CreateJob(create_job)) => {
+                CREATED_JOBS.save(
+                    storage,
+                    create_job.job_id.clone(),
+                    &Job {
+                        id: create_job.job_id,
+                        owner: sender.to_string(),
+                        chain_type: create_job.chain_type,
+                        chain_reference_id: create_job.chain_reference_id,
+                        is_payload_modifiable: create_job.payload_modifiable,
+                    },
+                )?;
+            }
+            PalomaMsg::SkywayMsg(SkywayMsg::SetErc20ToDenom(set_erc20_to_denom)) => {
+                ERC20_TO_DENOM.save(
+                    storage,
+                    set_erc20_to_denom.chain_reference_id.clone(),
+                    &set_erc20_to_denom,
+                )?;
+            }
+            PalomaMsg::SkywayMsg(SkywayMsg::SendTx(send_tx)) => {
+                // Skyway takes the coins out of circulation until the transfer is relayed
+                let amount: Coin = send_tx.amount.parse()?;
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender.clone(),
+                    BankMsg::Burn {
+                        amount: vec![amount],
+                    }
+                    .into(),
+                )?;
+                let transaction_id = LAST_SKYWAY_TX_ID.may_load(storage)?.unwrap_or_default() + 1;
+                LAST_SKYWAY_TX_ID.save(storage, &transaction_id)?;
+                SKYWAY_TXS.save(storage, transaction_id, &(sender, send_tx))?;
+                return Ok(AppResponse {
+                    events: vec![Event::new("outgoing_tx")
+                        .add_attribute("outgoing_tx_id", transaction_id.to_string())],
+                    data: None,
+                });
+            }
+            PalomaMsg::SkywayMsg(SkywayMsg::CancelTx(cancel_tx)) => {
+                let Some((tx_sender, send_tx)) =
+                    SKYWAY_TXS.may_load(storage, cancel_tx.transaction_id)?
+                else {
+                    bail!("Transaction {} not found", cancel_tx.transaction_id);
+                };
+                if tx_sender != sender {
+                    bail!("Not the sender of the transaction");
+                }
+                SKYWAY_TXS.remove(storage, cancel_tx.transaction_id);
+                router.sudo(
+                    api,
+                    storage,
+                    block,
+                    BankSudo::Mint {
+                        to_address: sender.to_string(),
+                        amount: vec![send_tx.amount.parse()?],
+                    }
+                    .into(),
+                )?;
+            }
+            PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::CreateDenom(create_denom)) => {
+                let denom = format!("factory/{}/{}", sender, create_denom.subdenom);
+                DENOM_ADMINS.save(storage, denom, &sender.to_string())?;
+            }
+            PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::MintTokens(mint)) => {
+                router.sudo(
+                    api,
+                    storage,
+                    block,
+                    BankSudo::Mint {
+                        to_address: mint.mint_to_address,
+                        amount: coins(mint.amount.u128(), mint.denom),
+                    }
+                    .into(),
+                )?;
+            }
+            PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::BurnTokens(burn)) => {
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender,
+                    BankMsg::Burn {
+                        amount: coins(burn.amount.u128(), burn.denom),
+                    }
+                    .into(),
+                )?;
+            }
+            PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::ChangeAdmin(change_admin)) => {
+                DENOM_ADMINS.save(storage, change_admin.denom, &change_admin.new_admin_address)?;
+            }
+            PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::SetDenomMetadata(set_metadata)) => {
+                DENOM_METADATA.save(storage, set_metadata.denom, &set_metadata.metadata)?;
+            }
+        }
+        Ok(AppResponse::default())
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        match request {
+            PalomaQuery::Scheduler(SchedulerQuery::GetJobById { job_id }) => {
+                let Some(job) = CREATED_JOBS.may_load(storage, job_id.clone())? else {
+                    bail!("Job {} not found", job_id);
+                };
+                Ok(to_json_binary(&JobResponse { job })?)
+            }
+            PalomaQuery::Evm(EvmQuery::GetChainInfo { chain_reference_id }) => {
+                let Some(active) = CHAIN_STATUS.may_load(storage, chain_reference_id.clone())?
+                else {
+                    bail!("Chain {} not found", chain_reference_id);
+                };
+                Ok(to_json_binary(&ChainInfoResponse {
+                    chain_reference_id,
+                    active,
+                })?)
+            }
+            request => bail!("{:?} is not supported", request),
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("Paloma sudo is not supported")
+    }
+}
+
+pub type PalomaApp =
+    App<BankKeeper, MockApi, MockStorage, PalomaModule, WasmKeeper<PalomaMsg, PalomaQuery>>;
+
+pub struct Suite {
+    pub app: PalomaApp,
+    pub code_id: u64,
+    pub contract: Addr,
+    pub owner: Addr,
+    pub minter: Addr,
+    pub user: Addr,
+}
+
+/// Instantiates the contract with `INVENTORY` PUSD and registers `CHAIN_ID`,
+/// active on Paloma and using the existing `JOB_ID`.
+pub fn setup() -> Suite {
+    let api = MockApi::default().with_prefix("paloma");
+    let owner = api.addr_make("owner");
+    let minter = api.addr_make("minter");
+    let user = api.addr_make("user");
+    let mut app = AppBuilder::new_custom()
+        .with_api(api)
+        .with_custom(PalomaModule)
+        .build(|router, _, storage| {
+            // The owner funds the contract inventory on instantiation
+            router
+                .bank
+                .init_balance(storage, &owner, vec![coin(INVENTORY + USER_BALANCE, DENOM)])
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &user, vec![coin(USER_BALANCE, DENOM)])
+                .unwrap();
+        });
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_reply(reply),
+    ));
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                retry_delay: RETRY_DELAY,
+                minter: minter.clone(),
+                denom: DENOM.to_string(),
+                decimals: None,
+                timelock_delay: None,
+                import: None,
+            },
+            &[coin(INVENTORY, DENOM)],
+            "pusd-manager",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+    let mut suite = Suite {
+        app,
+        code_id,
+        contract,
+        owner,
+        minter,
+        user,
+    };
+    suite.create_job(JOB_ID);
+    suite.set_chain_active(CHAIN_ID, true);
+    suite.register_chain(CHAIN_ID, chain_setting());
+    suite
+}
+
+pub fn chain_setting() -> ChainSetting {
+    ChainSetting {
+        job_id: JOB_ID.to_string(),
+        minimum_amount: Uint128::new(MINIMUM_AMOUNT),
+        decimals: None,
+        allowlist_only: false,
+        expiry_window: None,
+        retry_policy: None,
+        keeper_reward: None,
+        admin_job_id: None,
+        job_hints: None,
+        transport: Transport::Compass,
+        quota: None,
+    }
+}
+
+impl Suite {
+    pub fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg, funds: &[Coin]) -> AppResponse {
+        self.app
+            .execute_contract(sender.clone(), self.contract.clone(), msg, funds)
+            .unwrap()
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), msg)
+            .unwrap()
+    }
+
+    pub fn register_chain(&mut self, chain_id: &str, chain_setting: ChainSetting) {
+        let owner = self.owner.clone();
+        self.execute(
+            &owner,
+            &ExecuteMsg::RegisterChain {
+                chain_id: chain_id.to_string(),
+                chain_setting,
+            },
+            &[],
+        );
+    }
+
+    /// Withdraws `amount` from `sender` to `RECIPIENT` on `CHAIN_ID` and returns the nonce.
+    pub fn withdraw(&mut self, sender: &Addr, amount: u128) -> u64 {
+        self.execute(
+            sender,
+            &ExecuteMsg::Withdraw {
+                chain_id: CHAIN_ID.to_string(),
+                recipient: RECIPIENT.to_string(),
+                on_behalf_of: None,
+                refund_address: None,
+            },
+            &[coin(amount, DENOM)],
+        );
+        self.query::<crate::state::State>(&QueryMsg::GetState {})
+            .last_nonce
+    }
+
+    pub fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(seconds);
+        });
+    }
+
+    pub fn balance(&self, address: &Addr) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, DENOM)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    pub fn jobs(&self) -> Vec<ExecuteJob> {
+        self.app
+            .read_module(|_, _, storage| JOBS.may_load(storage).unwrap().unwrap_or_default())
+    }
+
+    pub fn erc20_to_denom(&self, chain_reference_id: &str) -> Option<SetErc20ToDenom> {
+        self.app.read_module(|_, _, storage| {
+            ERC20_TO_DENOM
+                .may_load(storage, chain_reference_id.to_string())
+                .unwrap()
+        })
+    }
+
+    /// Makes a scheduler job known to the mock, as if created by its owner.
+    pub fn create_job(&mut self, job_id: &str) {
+        let owner = self.owner.to_string();
+        self.app.init_modules(|_, _, storage| {
+            CREATED_JOBS
+                .save(
+                    storage,
+                    job_id.to_string(),
+                    &Job {
+                        id: job_id.to_string(),
+                        owner,
+                        chain_type: "evm".to_string(),
+                        chain_reference_id: CHAIN_ID.to_string(),
+                        is_payload_modifiable: true,
+                    },
+                )
+                .unwrap()
+        });
+    }
+
+    pub fn remove_job(&mut self, job_id: &str) {
+        self.app
+            .init_modules(|_, _, storage| CREATED_JOBS.remove(storage, job_id.to_string()));
+    }
+
+    pub fn set_chain_active(&mut self, chain_reference_id: &str, active: bool) {
+        self.app.init_modules(|_, _, storage| {
+            CHAIN_STATUS
+                .save(storage, chain_reference_id.to_string(), &active)
+                .unwrap()
+        });
+    }
+
+    /// Skyway transfers not relayed or cancelled yet.
+    pub fn skyway_txs(&self) -> Vec<(u64, SendTx)> {
+        self.app.read_module(|_, _, storage| {
+            SKYWAY_TXS
+                .range(storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(id, (_, send_tx))| (id, send_tx)))
+                .collect::<StdResult<_>>()
+                .unwrap()
+        })
+    }
+
+    /// Relays a Skyway transfer, after which it can no longer be cancelled.
+    pub fn relay_skyway_tx(&mut self, transaction_id: u64) {
+        self.app
+            .init_modules(|_, _, storage| SKYWAY_TXS.remove(storage, transaction_id));
+    }
+
+    pub fn denom_admin(&self, denom: &str) -> Option<String> {
+        self.app
+            .read_module(|_, _, storage| DENOM_ADMINS.may_load(storage, denom.to_string()).unwrap())
+    }
+
+    pub fn canonical(&self, address: &Addr) -> Vec<u8> {
+        self.app
+            .api()
+            .addr_canonicalize(address.as_str())
+            .unwrap()
+            .to_vec()
+    }
+
+    pub fn set_raw<T: Serialize>(&mut self, key: &[u8], value: &T) {
+        let contract = self.contract.clone();
+        self.app
+            .contract_storage_mut(&contract)
+            .set(key, &cosmwasm_std::to_json_vec(value).unwrap());
+    }
+
+    pub fn remove_raw(&mut self, key: &[u8]) {
+        let contract = self.contract.clone();
+        self.app.contract_storage_mut(&contract).remove(key);
+    }
+
+    pub fn migrate(&mut self, msg: &MigrateMsg) -> AnyResult<AppResponse> {
+        self.app
+            .migrate_contract(self.owner.clone(), self.contract.clone(), msg, self.code_id)
+    }
+}
+
+/// Decodes the arguments of a Vyper `withdraw` call: sender, recipient, amount and nonce.
+pub fn decode_withdraw(payload: &Binary) -> Vec<Token> {
+    decode_call(
+        "withdraw",
+        &[
+            ParamType::FixedBytes(32),
+            ParamType::Address,
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+        ],
+        payload,
+    )
+}
+
+pub fn decode_call(name: &str, params: &[ParamType], payload: &Binary) -> Vec<Token> {
+    assert_eq!(
+        payload[..4],
+        ethabi::short_signature(name, params),
+        "unexpected function selector"
+    );
+    ethabi::decode(params, &payload[4..]).unwrap()
+}
+
+pub fn uint(value: u128) -> Token {
+    Token::Uint(value.into())
+}
+
+pub fn address(value: &str) -> Token {
+    Token::Address(value.parse().unwrap())
+}
@@ -0,0 +1,76 @@
+use cosmwasm_std::{to_json_string, Uint128};
+
+use crate::msg::{
+    CancelTx, ChangeAdminMsg, ExecuteJob, MintMsg, PalomaMsg, PalomaQuery, SchedulerMsg,
+    SchedulerQuery, SendTx, SetErc20ToDenom, SkywayMsg, TokenFactoryMsg,
+};
+
+#[test]
+fn existing_messages_keep_their_wire_format() {
+    let execute_job = PalomaMsg::SchedulerMsg(SchedulerMsg::ExecuteJob(ExecuteJob {
+        job_id: "job".to_string(),
+        payload: b"payload".into(),
+        gas_limit: None,
+        priority: None,
+    }));
+    assert_eq!(
+        to_json_string(&execute_job).unwrap(),
+        r#"{"scheduler_msg":{"execute_job":{"job_id":"job","payload":"cGF5bG9hZA=="}}}"#
+    );
+
+    let set_erc20_to_denom = PalomaMsg::SkywayMsg(SkywayMsg::SetErc20ToDenom(SetErc20ToDenom {
+        erc20_address: "0xabc".to_string(),
+        token_denom: "upusd".to_string(),
+        chain_reference_id: "ethereum".to_string(),
+    }));
+    assert_eq!(
+        to_json_string(&set_erc20_to_denom).unwrap(),
+        r#"{"skyway_msg":{"set_erc20_to_denom":{"erc20_address":"0xabc","token_denom":"upusd","chain_reference_id":"ethereum"}}}"#
+    );
+
+    let change_admin = PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::ChangeAdmin(ChangeAdminMsg {
+        denom: "upusd".to_string(),
+        new_admin_address: "paloma1minter".to_string(),
+    }));
+    assert_eq!(
+        to_json_string(&change_admin).unwrap(),
+        r#"{"token_factory_msg":{"change_admin":{"denom":"upusd","new_admin_address":"paloma1minter"}}}"#
+    );
+}
+
+#[test]
+fn new_messages_and_queries_follow_paloma_bindings() {
+    let send_tx = PalomaMsg::SkywayMsg(SkywayMsg::SendTx(SendTx {
+        remote_chain_destination_address: "0xabc".to_string(),
+        amount: "1000upusd".to_string(),
+        chain_reference_id: "ethereum".to_string(),
+    }));
+    assert_eq!(
+        to_json_string(&send_tx).unwrap(),
+        r#"{"skyway_msg":{"send_tx":{"remote_chain_destination_address":"0xabc","amount":"1000upusd","chain_reference_id":"ethereum"}}}"#
+    );
+
+    let cancel_tx = PalomaMsg::SkywayMsg(SkywayMsg::CancelTx(CancelTx { transaction_id: 7 }));
+    assert_eq!(
+        to_json_string(&cancel_tx).unwrap(),
+        r#"{"skyway_msg":{"cancel_tx":{"transaction_id":7}}}"#
+    );
+
+    let mint = PalomaMsg::TokenFactoryMsg(TokenFactoryMsg::MintTokens(MintMsg {
+        denom: "upusd".to_string(),
+        amount: Uint128::new(5),
+        mint_to_address: "paloma1user".to_string(),
+    }));
+    assert_eq!(
+        to_json_string(&mint).unwrap(),
+        r#"{"token_factory_msg":{"mint_tokens":{"denom":"upusd","amount":"5","mint_to_address":"paloma1user"}}}"#
+    );
+
+    let get_job = PalomaQuery::Scheduler(SchedulerQuery::GetJobById {
+        job_id: "job".to_string(),
+    });
+    assert_eq!(
+        to_json_string(&get_job).unwrap(),
+        r#"{"scheduler":{"get_job_by_id":{"job_id":"job"}}}"#
+    );
+}
@@ -0,0 +1,135 @@
+use cosmwasm_std::{Timestamp, Uint128};
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg, UserQuotaResponse};
+use crate::state::{ComplianceList, Quota, QUOTA_BUCKET, QUOTA_BUCKETS};
+
+const MAX_AMOUNT: u128 = 12_000;
+const MAX_COUNT: u32 = 3;
+const DAY: u64 = QUOTA_BUCKET * QUOTA_BUCKETS;
+
+fn setup_quota() -> Suite {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                quota: Some(Quota {
+                    max_amount: Some(Uint128::new(MAX_AMOUNT)),
+                    max_count: Some(MAX_COUNT),
+                }),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite
+}
+
+fn user_quota(suite: &Suite, address: &Addr) -> UserQuotaResponse {
+    suite.query(&QueryMsg::UserQuota {
+        address: address.to_string(),
+        chain_id: CHAIN_ID.to_string(),
+    })
+}
+
+#[test]
+fn withdrawals_are_counted_against_daily_quota() {
+    let mut suite = setup_quota();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 5_000);
+    suite.withdraw(&user, 4_000);
+
+    let quota = user_quota(&suite, &user);
+    assert!(!quota.exempt);
+    assert_eq!(quota.used_amount, Uint128::new(9_000));
+    assert_eq!(quota.used_count, 2);
+    assert_eq!(
+        quota.remaining_amount,
+        Some(Uint128::new(MAX_AMOUNT - 9_000))
+    );
+    assert_eq!(quota.remaining_count, Some(1));
+    let time = suite.app.block_info().time.seconds();
+    assert_eq!(
+        quota.resets_at,
+        Timestamp::from_seconds((time / QUOTA_BUCKET) * QUOTA_BUCKET + DAY)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Daily withdraw amount exceeded")]
+fn withdraw_above_daily_amount_fails() {
+    let mut suite = setup_quota();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 8_000);
+
+    suite.withdraw(&user, MAX_AMOUNT - 8_000 + 1);
+}
+
+#[test]
+#[should_panic(expected = "Daily withdraw count exceeded")]
+fn withdraw_above_daily_count_fails() {
+    let mut suite = setup_quota();
+    let user = suite.user.clone();
+    for _ in 0..MAX_COUNT {
+        suite.withdraw(&user, 2_000);
+    }
+
+    suite.withdraw(&user, 2_000);
+}
+
+#[test]
+fn quota_frees_up_after_a_rolling_day() {
+    let mut suite = setup_quota();
+    let user = suite.user.clone();
+    suite.withdraw(&user, 4_000);
+    suite.advance(QUOTA_BUCKET);
+    suite.withdraw(&user, MAX_AMOUNT - 4_000);
+
+    suite.advance(DAY - QUOTA_BUCKET);
+    suite.withdraw(&user, 4_000);
+
+    let quota = user_quota(&suite, &user);
+    assert_eq!(quota.used_amount, Uint128::new(MAX_AMOUNT));
+    assert_eq!(quota.used_count, 2);
+}
+
+#[test]
+#[should_panic(expected = "Daily withdraw amount exceeded")]
+fn quota_does_not_reset_at_midnight() {
+    let mut suite = setup_quota();
+    let user = suite.user.clone();
+    let time = suite.app.block_info().time.seconds();
+    suite.advance(DAY - time % DAY - 1);
+    suite.withdraw(&user, MAX_AMOUNT);
+
+    suite.advance(2);
+    suite.withdraw(&user, MAX_AMOUNT);
+}
+
+#[test]
+fn allowlisted_accounts_are_exempt() {
+    let mut suite = setup_quota();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateComplianceList {
+            list: ComplianceList::Allowlist,
+            add: vec![user.to_string()],
+            remove: vec![],
+        },
+        &[],
+    );
+
+    for _ in 0..=MAX_COUNT {
+        suite.withdraw(&user, MAX_AMOUNT);
+    }
+
+    let quota = user_quota(&suite, &user);
+    assert!(quota.exempt);
+    assert_eq!(quota.remaining_amount, None);
+    assert_eq!(quota.used_count, 0);
+}
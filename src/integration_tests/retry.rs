@@ -0,0 +1,284 @@
+use cosmwasm_std::Uint128;
+use cw_multi_test::AppResponse;
+use ethabi::Token;
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg, SolvencyResponse};
+use crate::state::{BurnInfo, ComplianceList, RetryPolicy, WithdrawStatus};
+
+const BASE_DELAY: u64 = 100;
+const MAX_DELAY: u64 = 300;
+const MAX_ATTEMPTS: u32 = 3;
+
+fn setup_retry_policy() -> Suite {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                retry_policy: Some(RetryPolicy {
+                    base_delay: BASE_DELAY,
+                    multiplier: 2,
+                    max_delay: MAX_DELAY,
+                    max_attempts: MAX_ATTEMPTS,
+                }),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite
+}
+
+/// Retries `nonce` once per delay of the policy until the attempts are exhausted.
+fn exhaust_retries(suite: &mut Suite, nonce: u64) {
+    let user = suite.user.clone();
+    for delay in [BASE_DELAY, 2 * BASE_DELAY, MAX_DELAY] {
+        suite.advance(delay + 1);
+        suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+    }
+}
+
+#[test]
+fn retries_back_off_until_review_is_needed() {
+    let mut suite = setup_retry_policy();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    exhaust_retries(&mut suite, nonce);
+    assert_eq!(suite.jobs().len(), 4);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.attempts, MAX_ATTEMPTS);
+    assert_eq!(burn_info.status, WithdrawStatus::Pending);
+
+    suite.advance(MAX_DELAY + 1);
+    let response = suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-pusd_needs_review"));
+    assert_eq!(suite.jobs().len(), 4);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.status, WithdrawStatus::NeedsReview);
+}
+
+#[test]
+#[should_panic(expected = "Retry delay not reached")]
+fn retry_before_backoff_delay_fails() {
+    let mut suite = setup_retry_policy();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+    suite.advance(BASE_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    suite.advance(2 * BASE_DELAY);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Withdraw needs review")]
+fn retry_of_withdraw_needing_review_fails() {
+    let mut suite = setup_retry_policy();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+    exhaust_retries(&mut suite, nonce);
+    suite.advance(MAX_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    suite.advance(MAX_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+}
+
+#[test]
+fn reset_retries_allows_retrying_again() {
+    let mut suite = setup_retry_policy();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+    exhaust_retries(&mut suite, nonce);
+    suite.advance(MAX_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    suite.execute(&owner, &ExecuteMsg::ResetRetries { nonce }, &[]);
+    suite.advance(BASE_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    assert_eq!(suite.jobs().len(), 5);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.attempts, 1);
+    assert_eq!(burn_info.status, WithdrawStatus::Pending);
+}
+
+#[test]
+#[should_panic(expected = "Withdraw is pending")]
+fn cancel_before_backoff_delay_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let user = suite.user.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                retry_policy: Some(RetryPolicy {
+                    base_delay: 2 * RETRY_DELAY,
+                    multiplier: 2,
+                    max_delay: 4 * RETRY_DELAY,
+                    max_attempts: MAX_ATTEMPTS,
+                }),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+}
+
+fn retry_batch(suite: &mut Suite, keeper: &Addr) -> AppResponse {
+    retry_batch_after(suite, keeper, None)
+}
+
+fn retry_batch_after(suite: &mut Suite, keeper: &Addr, start_after: Option<u64>) -> AppResponse {
+    suite.execute(
+        keeper,
+        &ExecuteMsg::RetryBatch {
+            chain_id: CHAIN_ID.to_string(),
+            start_after,
+            limit: None,
+        },
+        &[],
+    )
+}
+
+fn next_start_after(response: &AppResponse) -> Option<String> {
+    response
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-pusd_retry_batch")
+        .flat_map(|event| &event.attributes)
+        .find(|attribute| attribute.key == "next_start_after")
+        .map(|attribute| attribute.value.clone())
+}
+
+#[test]
+fn keeper_retries_due_withdrawals_for_burner_and_is_rewarded() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let keeper = suite.app.api().addr_make("keeper");
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                keeper_reward: Some(Uint128::new(10)),
+                retry_policy: Some(RetryPolicy {
+                    base_delay: RETRY_DELAY,
+                    multiplier: 2,
+                    max_delay: MAX_DELAY * 10,
+                    max_attempts: MAX_ATTEMPTS,
+                }),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    suite.execute(&owner, &ExecuteMsg::FundKeeperPool {}, &[coin(25, DENOM)]);
+    for _ in 0..3 {
+        suite.withdraw(&user, 5_000);
+    }
+
+    suite.advance(RETRY_DELAY + 1);
+    retry_batch(&mut suite, &keeper);
+
+    let jobs = suite.jobs();
+    assert_eq!(jobs.len(), 6);
+    for (job, nonce) in jobs[3..].iter().zip(1..) {
+        let tokens = decode_withdraw(&job.payload);
+        assert_eq!(tokens[0], Token::FixedBytes(suite.canonical(&user)));
+        assert_eq!(tokens[3], uint(nonce));
+    }
+    // The reward is capped by the pool
+    assert_eq!(suite.balance(&keeper), 25);
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.keeper_pool, Uint128::zero());
+    assert!(solvency.solvent);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce: 1 });
+    assert_eq!(burn_info.attempts, 1);
+}
+
+#[test]
+#[should_panic(expected = "Keeper reward requires a retry policy")]
+fn keeper_reward_without_retry_policy_fails() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                keeper_reward: Some(Uint128::new(10)),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+}
+
+#[test]
+fn retry_batch_skips_withdrawals_not_due() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let keeper = suite.app.api().addr_make("keeper");
+    suite.withdraw(&user, 5_000);
+    suite.advance(RETRY_DELAY);
+    suite.withdraw(&user, 5_000);
+
+    suite.advance(1);
+    retry_batch(&mut suite, &keeper);
+
+    let jobs = suite.jobs();
+    assert_eq!(jobs.len(), 3);
+    assert_eq!(decode_withdraw(&jobs[2].payload)[3], uint(1));
+    assert_eq!(suite.balance(&keeper), 0);
+}
+
+#[test]
+fn retry_batch_scan_is_capped_and_continues_after_cursor() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let keeper = suite.app.api().addr_make("keeper");
+    for _ in 0..100 {
+        suite.withdraw(&user, 5_000);
+    }
+    let due = suite.withdraw(&owner, 5_000);
+    // The withdrawals of the denylisted user are skipped
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateComplianceList {
+            list: ComplianceList::SenderDenylist,
+            add: vec![user.to_string()],
+            remove: vec![],
+        },
+        &[],
+    );
+    suite.advance(RETRY_DELAY + 1);
+
+    let response = retry_batch(&mut suite, &keeper);
+    assert_eq!(next_start_after(&response), Some("100".to_string()));
+    assert_eq!(suite.jobs().len(), 101);
+
+    let response = retry_batch_after(&mut suite, &keeper, Some(100));
+    assert_eq!(next_start_after(&response), None);
+    let jobs = suite.jobs();
+    assert_eq!(jobs.len(), 102);
+    assert_eq!(decode_withdraw(&jobs[101].payload)[3], uint(due as u128));
+}
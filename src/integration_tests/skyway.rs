@@ -0,0 +1,179 @@
+use cosmwasm_std::{coins, Uint128};
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg, SolvencyResponse};
+use crate::state::BurnInfo;
+
+const ERC20: &str = "0x0000000000000000000000000000000000000abc";
+
+fn use_skyway(suite: &mut Suite) {
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                transport: Transport::Skyway,
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+}
+
+fn setup_skyway() -> Suite {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    use_skyway(&mut suite);
+    suite.execute(
+        &owner,
+        &ExecuteMsg::SetBridge {
+            chain_reference_id: CHAIN_ID.to_string(),
+            erc20_address: ERC20.to_string(),
+        },
+        &[],
+    );
+    suite
+}
+
+#[test]
+fn withdraw_is_sent_through_skyway() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+
+    let nonce = suite.withdraw(&user, 5_000);
+
+    assert!(suite.jobs().is_empty());
+    let skyway_txs = suite.skyway_txs();
+    assert_eq!(skyway_txs.len(), 1);
+    let (transaction_id, send_tx) = &skyway_txs[0];
+    assert_eq!(send_tx.remote_chain_destination_address, RECIPIENT);
+    assert_eq!(send_tx.amount, format!("5000{}", DENOM));
+    assert_eq!(send_tx.chain_reference_id, CHAIN_ID);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.transport, Transport::Skyway);
+    assert_eq!(burn_info.skyway_tx_id, Some(*transaction_id));
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_in_transit, Uint128::new(5_000));
+    assert_eq!(solvency.total_escrowed, Uint128::zero());
+    assert!(solvency.solvent);
+}
+
+#[test]
+fn cancel_withdraw_cancels_skyway_transfer() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+
+    assert!(suite.skyway_txs().is_empty());
+    assert_eq!(suite.balance(&user), USER_BALANCE);
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_in_transit, Uint128::zero());
+    assert_eq!(solvency.balance, Uint128::new(INVENTORY));
+}
+
+#[test]
+#[should_panic(expected = "Transaction 1 not found")]
+fn cancel_after_relay_fails() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+    suite.relay_skyway_tx(1);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+}
+
+#[test]
+fn burn_pusd_confirms_relayed_transfer() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+    suite.relay_skyway_tx(1);
+
+    suite.execute(&owner, &ExecuteMsg::BurnPusd { nonce }, &[]);
+
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_in_transit, Uint128::zero());
+    assert_eq!(solvency.total_burned, Uint128::new(5_000));
+    assert!(solvency.solvent);
+}
+
+#[test]
+fn expired_skyway_transfers_are_not_refunded_in_batches() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let keeper = suite.app.api().addr_make("keeper");
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                transport: Transport::Skyway,
+                expiry_window: Some(RETRY_DELAY),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+    let nonce = suite.withdraw(&user, 5_000);
+    suite.relay_skyway_tx(1);
+
+    suite.advance(2 * RETRY_DELAY);
+    suite.execute(
+        &keeper,
+        &ExecuteMsg::RefundExpired {
+            chain_id: CHAIN_ID.to_string(),
+            start_after: None,
+            limit: None,
+        },
+        &[],
+    );
+
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.amount, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "Invalid EVM address")]
+fn skyway_withdraw_to_invalid_recipient_fails() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+
+    suite.execute(
+        &user,
+        &ExecuteMsg::Withdraw {
+            chain_id: CHAIN_ID.to_string(),
+            recipient: "not an address".to_string(),
+            on_behalf_of: None,
+            refund_address: None,
+        },
+        &coins(5_000, DENOM),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Bridge not set")]
+fn skyway_withdraw_without_bridge_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    use_skyway(&mut suite);
+
+    suite.withdraw(&user, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "Withdraw is relayed by Skyway")]
+fn re_withdraw_of_skyway_transfer_fails() {
+    let mut suite = setup_skyway();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+}
@@ -0,0 +1,376 @@
+use cosmwasm_std::{Event, Uint128, Uint256};
+use ethabi::Token;
+
+use super::*;
+use crate::msg::{ExecuteMsg, QueryMsg, ReconciliationResponse, SolvencyResponse};
+use crate::state::{BurnInfo, ChainAccounting};
+
+#[test]
+fn withdraw_escrows_funds_and_dispatches_job() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+
+    let nonce = suite.withdraw(&user, 5_000);
+
+    assert_eq!(nonce, 1);
+    assert_eq!(suite.balance(&user), USER_BALANCE - 5_000);
+    assert_eq!(suite.balance(&suite.contract), INVENTORY + 5_000);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.burner, user);
+    assert_eq!(burn_info.amount, 5_000);
+    assert_eq!(burn_info.remote_amount, Some(Uint256::from(5_000u128)));
+
+    let jobs = suite.jobs();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].job_id, JOB_ID);
+    assert_eq!(
+        decode_withdraw(&jobs[0].payload),
+        vec![
+            Token::FixedBytes(suite.canonical(&user)),
+            address(RECIPIENT),
+            uint(5_000),
+            uint(1),
+        ]
+    );
+}
+
+#[test]
+fn withdraw_emits_typed_event() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+
+    let response = suite.execute(
+        &user,
+        &ExecuteMsg::Withdraw {
+            chain_id: CHAIN_ID.to_string(),
+            recipient: RECIPIENT.to_string(),
+            on_behalf_of: None,
+            refund_address: None,
+        },
+        &[coin(5_000, DENOM)],
+    );
+
+    let event = response
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-pusd_withdraw")
+        .unwrap();
+    let attributes: Vec<(&str, &str)> = event
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.key != "_contract_address")
+        .map(|attribute| (attribute.key.as_str(), attribute.value.as_str()))
+        .collect();
+    assert_eq!(
+        attributes,
+        vec![
+            ("nonce", "1"),
+            ("chain_id", CHAIN_ID),
+            ("burner", user.as_str()),
+            ("recipient", RECIPIENT),
+            ("amount", "5000"),
+            ("remote_amount", "5000"),
+            ("dust", "0"),
+            ("denom", DENOM),
+            ("transport", "compass"),
+        ]
+    );
+    assert!(response.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "withdraw")
+            .add_attribute("_contract_address", suite.contract.as_str())
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Amount must be greater than minimum amount")]
+fn withdraw_below_minimum_amount_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    suite.withdraw(&user, MINIMUM_AMOUNT);
+}
+
+#[test]
+fn withdraw_scales_to_remote_decimals() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                decimals: Some(18),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+
+    suite.withdraw(&user, 5_000);
+
+    let jobs = suite.jobs();
+    assert_eq!(
+        decode_withdraw(&jobs[0].payload)[2],
+        uint(5_000 * 10u128.pow(12))
+    );
+}
+
+#[test]
+fn withdraw_refunds_dust_below_remote_decimals() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    suite.execute(
+        &owner,
+        &ExecuteMsg::UpdateChain {
+            chain_id: CHAIN_ID.to_string(),
+            chain_setting: ChainSetting {
+                decimals: Some(4),
+                ..chain_setting()
+            },
+        },
+        &[],
+    );
+
+    let nonce = suite.withdraw(&user, 5_055);
+
+    assert_eq!(suite.balance(&user), USER_BALANCE - 5_000);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.amount, 5_000);
+    assert_eq!(burn_info.remote_amount, Some(Uint256::from(50u128)));
+    assert_eq!(decode_withdraw(&suite.jobs()[0].payload)[2], uint(50));
+}
+
+#[test]
+fn re_withdraw_dispatches_same_payload_after_delay() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+
+    let jobs = suite.jobs();
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0], jobs[1]);
+    let burn_info: BurnInfo = suite.query(&QueryMsg::GetBurnInfo { nonce });
+    assert_eq!(burn_info.timestamp, suite.app.block_info().time);
+}
+
+#[test]
+#[should_panic(expected = "Retry delay not reached")]
+fn re_withdraw_before_delay_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY);
+    suite.execute(&user, &ExecuteMsg::ReWithdraw { nonce }, &[]);
+}
+
+#[test]
+fn cancel_withdraw_refunds_burner() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+
+    assert_eq!(suite.balance(&user), USER_BALANCE);
+    assert_eq!(suite.balance(&suite.contract), INVENTORY);
+    let withdraw_list: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    assert!(withdraw_list.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Withdraw is pending")]
+fn cancel_withdraw_while_pending_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn cancel_withdraw_by_other_account_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&owner, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+}
+
+#[test]
+fn burn_pusd_completes_withdraw() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.execute(&owner, &ExecuteMsg::BurnPusd { nonce }, &[]);
+
+    let withdraw_list: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    assert!(withdraw_list.is_empty());
+    // The escrowed PUSD stays with the contract
+    assert_eq!(suite.balance(&suite.contract), INVENTORY + 5_000);
+    assert!(!suite.query::<bool>(&QueryMsg::ReWithdrawable {}));
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_escrowed, Uint128::zero());
+    assert_eq!(solvency.total_burned, Uint128::new(5_000));
+    assert_eq!(solvency.mint_inventory, Uint128::new(INVENTORY + 5_000));
+    let chain_accounting: ChainAccounting = suite.query(&QueryMsg::GetChainAccounting {
+        chain_id: CHAIN_ID.to_string(),
+    });
+    assert_eq!(chain_accounting.total_withdrawn, Uint128::new(5_000));
+    assert_eq!(chain_accounting.total_burned, Uint128::new(5_000));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn burn_pusd_by_non_owner_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.execute(&user, &ExecuteMsg::BurnPusd { nonce }, &[]);
+}
+
+#[test]
+fn nonces_increase_per_withdraw() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+
+    assert_eq!(suite.withdraw(&user, 2_000), 1);
+    assert_eq!(suite.withdraw(&user, 3_000), 2);
+
+    let withdraw_list: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    let amounts: Vec<(u64, u128)> = withdraw_list
+        .into_iter()
+        .map(|(nonce, burn_info)| (nonce, burn_info.amount))
+        .collect();
+    assert_eq!(amounts, vec![(1, 2_000), (2, 3_000)]);
+    assert_eq!(
+        suite
+            .query::<crate::msg::BalanceResponse>(&QueryMsg::PusdBalance {})
+            .balance,
+        Uint128::new(INVENTORY + 5_000)
+    );
+}
+
+fn withdraw_with_refund_address(suite: &mut Suite, refund_address: &Addr) -> u64 {
+    let user = suite.user.clone();
+    suite.execute(
+        &user,
+        &ExecuteMsg::Withdraw {
+            chain_id: CHAIN_ID.to_string(),
+            recipient: RECIPIENT.to_string(),
+            on_behalf_of: None,
+            refund_address: Some(refund_address.clone()),
+        },
+        &[coin(5_000, DENOM)],
+    );
+    suite
+        .query::<crate::state::State>(&QueryMsg::GetState {})
+        .last_nonce
+}
+
+#[test]
+fn cancel_withdraw_refunds_refund_address() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let exchange = suite.app.api().addr_make("exchange");
+    let nonce = withdraw_with_refund_address(&mut suite, &exchange);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce }, &[]);
+
+    assert_eq!(suite.balance(&exchange), 5_000);
+    assert_eq!(suite.balance(&user), USER_BALANCE - 5_000);
+}
+
+#[test]
+fn force_refund_by_owner_refunds_refund_address() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+    let exchange = suite.app.api().addr_make("exchange");
+    let nonce = withdraw_with_refund_address(&mut suite, &exchange);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&owner, &ExecuteMsg::ForceRefund { nonce }, &[]);
+
+    assert_eq!(suite.balance(&exchange), 5_000);
+    let withdraw_list: Vec<(u64, BurnInfo)> = suite.query(&QueryMsg::GetWithdrawList {});
+    assert!(withdraw_list.is_empty());
+    let solvency: SolvencyResponse = suite.query(&QueryMsg::Solvency {});
+    assert_eq!(solvency.total_escrowed, Uint128::zero());
+}
+
+#[test]
+#[should_panic(expected = "Withdraw is pending")]
+fn force_refund_while_pending_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.execute(&owner, &ExecuteMsg::ForceRefund { nonce }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn force_refund_by_burner_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let nonce = suite.withdraw(&user, 5_000);
+
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::ForceRefund { nonce }, &[]);
+}
+
+#[test]
+#[should_panic(expected = "Chain is not active")]
+fn withdraw_to_inactive_chain_fails() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    suite.set_chain_active(CHAIN_ID, false);
+
+    suite.withdraw(&user, 5_000);
+}
+
+#[test]
+fn reconciliation_summarizes_chain_outflow() {
+    let mut suite = setup();
+    let user = suite.user.clone();
+    let owner = suite.owner.clone();
+    for amount in [5_000, 6_000, 7_000] {
+        suite.withdraw(&user, amount);
+    }
+    suite.execute(&owner, &ExecuteMsg::BurnPusd { nonce: 2 }, &[]);
+    suite.advance(RETRY_DELAY + 1);
+    suite.execute(&user, &ExecuteMsg::CancelWithdraw { nonce: 1 }, &[]);
+
+    let reconciliation: ReconciliationResponse = suite.query(&QueryMsg::Reconciliation {
+        chain_id: CHAIN_ID.to_string(),
+    });
+
+    assert_eq!(
+        reconciliation,
+        ReconciliationResponse {
+            chain_id: CHAIN_ID.to_string(),
+            total_withdrawn: Uint128::new(18_000),
+            total_burned: Uint128::new(6_000),
+            total_cancelled: Uint128::new(5_000),
+            pending_amount: Uint128::new(7_000),
+            pending_count: 1,
+            oldest_pending_nonce: Some(3),
+            oldest_pending_age: Some(RETRY_DELAY + 1),
+            balanced: true,
+        }
+    );
+}
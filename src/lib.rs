@@ -1,6 +1,10 @@
 pub mod contract;
 mod error;
+pub mod events;
 pub mod helpers;
+#[cfg(test)]
+mod integration_tests;
+mod migrations;
 pub mod msg;
 pub mod state;
 
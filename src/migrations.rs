@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, DepsMut, Empty, Env, Order, StdError, StdResult, Storage, Uint128};
+use cw2::get_contract_version;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::msg::{MigrateMsg, PalomaQuery};
+use crate::state::{
+    default_decimals, Accounting, BurnInfo, ChainAccounting, State, ACCOUNTING, CHAIN_ACCOUNTING,
+    CHAIN_WITHDRAWALS, STATE, WITHDRAW_LIST,
+};
+
+type MigrationStep = fn(&mut dyn Storage, &Env, &MigrateMsg) -> Result<(), ContractError>;
+
+// Ordered step migrations. A step is applied when the stored contract version is
+// lower than the version that introduced it.
+const MIGRATIONS: &[(&str, &str, MigrationStep)] = &[
+    ("0.1.0", "add_minter", add_minter),
+    ("0.2.0", "backfill_accounting", backfill_accounting),
+    ("0.2.0", "index_withdrawals", index_withdrawals),
+];
+
+/// Applies the migrations newer than the stored version, returning the stored
+/// version and the names of the applied steps.
+pub fn migrate(
+    deps: DepsMut<PalomaQuery>,
+    env: &Env,
+    msg: &MigrateMsg,
+    contract_name: &str,
+    contract_version: &str,
+) -> Result<(String, Vec<&'static str>), ContractError> {
+    // Instances created before cw2 versioning have no stored version
+    let stored_version = match get_contract_version(deps.storage) {
+        Ok(stored) => {
+            if stored.contract != contract_name {
+                return Err(ContractError::InvalidContractName {
+                    stored: stored.contract,
+                });
+            }
+            stored.version
+        }
+        Err(_) => "0.0.0".to_string(),
+    };
+    let stored = parse_version(&stored_version)?;
+    if stored > parse_version(contract_version)? {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored_version,
+            current: contract_version.to_string(),
+        });
+    }
+
+    let mut applied = vec![];
+    for (version, name, step) in MIGRATIONS {
+        if stored < parse_version(version)? {
+            step(deps.storage, env, msg)?;
+            applied.push(*name);
+        }
+    }
+    Ok((stored_version, applied))
+}
+
+fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let parts = version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| StdError::generic_err(format!("Invalid version {}", version)))?;
+    match parts.as_slice() {
+        [major, minor, patch] => Ok((*major, *minor, *patch)),
+        _ => Err(StdError::generic_err(format!("Invalid version {}", version)).into()),
+    }
+}
+
+// The first release stored State without a minter
+fn add_minter(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    #[derive(Serialize, Deserialize)]
+    struct OldState {
+        pub retry_delay: u64,
+        pub owner: Addr,
+        pub denom: String,
+        pub last_nonce: u64,
+    }
+
+    let Some(data) = storage.get(b"state") else {
+        return Err(ContractError::MigrationFailed {});
+    };
+    if cosmwasm_std::from_json::<State>(&data).is_ok() {
+        return Ok(());
+    }
+    let Some(minter) = msg.minter.clone() else {
+        return Err(ContractError::MigrationFailed {});
+    };
+    let old_state: OldState = cosmwasm_std::from_json(&data)?;
+    STATE.save(
+        storage,
+        &State {
+            retry_delay: old_state.retry_delay,
+            owner: old_state.owner,
+            minter,
+            denom: old_state.denom,
+            last_nonce: old_state.last_nonce,
+            decimals: default_decimals(),
+            timelock_delay: 0,
+        },
+    )?;
+    Ok(())
+}
+
+// Counters were introduced with withdrawals already pending
+fn backfill_accounting(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    // Store the fields added with serde defaults explicitly
+    let state = STATE.load(storage)?;
+    STATE.save(storage, &state)?;
+
+    if !ACCOUNTING.exists(storage) {
+        let mut accounting = Accounting::default();
+        let mut chain_accounting: BTreeMap<String, ChainAccounting> = BTreeMap::new();
+        for item in WITHDRAW_LIST.range(storage, None, None, Order::Ascending) {
+            let burn_info = item?.1;
+            let amount = Uint128::from(burn_info.amount);
+            accounting.total_escrowed += amount;
+            // Pending withdrawals count as withdrawn, so chains reconcile
+            chain_accounting
+                .entry(burn_info.chain_id)
+                .or_default()
+                .total_withdrawn += amount;
+        }
+        ACCOUNTING.save(storage, &accounting)?;
+        for (chain_id, chain_accounting) in chain_accounting {
+            CHAIN_ACCOUNTING.save(storage, chain_id, &chain_accounting)?;
+        }
+    }
+    Ok(())
+}
+
+// Pending withdrawals are indexed per chain. Withdrawals from before created_at was
+// recorded keep the time of their last dispatch as creation time, expiry scans stop at
+// the first unexpired nonce so a late retry only delays the nonces after it.
+fn index_withdrawals(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    let withdrawals = WITHDRAW_LIST
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, BurnInfo)>>>()?;
+    for (nonce, mut burn_info) in withdrawals {
+        burn_info.created_at = Some(burn_info.created_at());
+        CHAIN_WITHDRAWALS.save(storage, (burn_info.chain_id.clone(), nonce), &Empty {})?;
+        WITHDRAW_LIST.save(storage, nonce, &burn_info)?;
+    }
+    Ok(())
+}
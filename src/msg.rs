@@ -1,12 +1,17 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, CustomMsg, Uint128};
+use cosmwasm_std::{Addr, Binary, CustomMsg, CustomQuery, Timestamp, Uint128};
 
 #[allow(unused_imports)]
-use crate::state::{BurnInfo, ChainSetting, State};
+use crate::state::{
+    Accounting, AdminAction, BridgeInfo, BurnInfo, ChainAccounting, ChainSetting,
+    ChainSettingChange, ComplianceList, ExpiredWithdrawal, MintApprovalConfig, MintRequest,
+    QueuedAdminAction, Redemption, RedemptionTotals, RemoteConfig, State, Transport,
+};
 
 #[cw_serde]
 pub struct MigrateMsg {
-    pub minter: Addr,
+    // New minter, also made admin of the denom; None keeps the current minter
+    pub minter: Option<Addr>,
 }
 
 #[cw_serde]
@@ -14,6 +19,10 @@ pub struct InstantiateMsg {
     pub retry_delay: u64,
     pub minter: Addr,
     pub denom: String,
+    pub decimals: Option<u8>,
+    pub timelock_delay: Option<u64>,
+    // First page of an ExportState bundle to restore, the other fields are then ignored
+    pub import: Option<StateExport>,
 }
 
 #[cw_serde]
@@ -23,6 +32,16 @@ pub enum ExecuteMsg {
         chain_id: String,
         chain_setting: ChainSetting,
     },
+    // Replace the setting of a registered chain
+    UpdateChain {
+        chain_id: String,
+        chain_setting: ChainSetting,
+    },
+    // Remove a chain, refused while it has pending withdrawals unless forced
+    DeregisterChain {
+        chain_id: String,
+        force: Option<bool>,
+    },
     SetBridge {
         chain_reference_id: String,
         erc20_address: String,
@@ -32,18 +51,59 @@ pub enum ExecuteMsg {
         recipient: Addr,
         amount: Uint128,
     },
+    // Enable, replace or disable (None) the approval flow for large mints
+    UpdateMintApprovalConfig {
+        config: Option<MintApprovalConfig>,
+    },
+    // Create a mint request that executes once enough approvers sign it
+    ProposeMint {
+        recipient: Addr,
+        amount: Uint128,
+    },
+    ApproveMint {
+        id: u64,
+    },
+    CancelMint {
+        id: u64,
+    },
     // Receive PUSD and keep with nonce until withdrawn by owner
     Withdraw {
         chain_id: String,
         recipient: String,
+        // Account the withdraw is attributed to, requires an allowance granted to the sender
+        on_behalf_of: Option<String>,
+        // Receives the refund if the withdraw is cancelled, defaults to the burner
+        refund_address: Option<Addr>,
+    },
+    // Allow spender to withdraw up to amount to chain_id on behalf of the sender
+    GrantAllowance {
+        spender: String,
+        chain_id: String,
+        amount: Uint128,
+    },
+    RevokeAllowance {
+        spender: String,
+        chain_id: String,
     },
     // ReWithdraw PUSD by nonce
     ReWithdraw {
         nonce: u64,
     },
 
+    // Owner only, while an import is in progress
+    ImportEntries {
+        entries: Vec<ExportEntry>,
+    },
+    // Check the imported entries against the export and end the import
+    FinalizeImport {},
+
+    // Takes PUSD out of the inventory, recorded as a redemption
     UnmintPusd {
         amount: Uint128,
+        // Off-chain collateral ID, unique across redemptions
+        reference: Option<String>,
+        // Burn the PUSD instead of sending it to the minter
+        burn: Option<bool>,
     },
 
     // Burn PUSD by nonce
@@ -54,10 +114,50 @@ pub enum ExecuteMsg {
     CancelWithdraw {
         nonce: u64,
     },
+    // Retry up to limit withdrawals of a chain that are due, by anyone
+    RetryBatch {
+        chain_id: String,
+        // Nonce to continue after, the next_start_after of the previous batch
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Add the sent PUSD to the pool paying RetryBatch rewards
+    FundKeeperPool {},
+    // Allow a withdraw that needs review to be retried again
+    ResetRetries {
+        nonce: u64,
+    },
+    // Refund up to limit withdrawals of a chain that are past its expiry window, by anyone
+    RefundExpired {
+        chain_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Refund a stuck withdraw by nonce, by the owner or the operator
+    ForceRefund {
+        nonce: u64,
+    },
     // Update Config
     UpdateConfig {
         retry_delay: Option<u64>,
         owner: Option<Addr>,
+        timelock_delay: Option<u64>,
+    },
+    // Queue a sensitive admin action, executable once the timelock delay has passed
+    ProposeAdminAction {
+        action: AdminAction,
+    },
+    ExecuteAdminAction {
+        id: u64,
+    },
+    CancelAdminAction {
+        id: u64,
+    },
+    // Add and remove members of a compliance list
+    UpdateComplianceList {
+        list: ComplianceList,
+        add: Vec<String>,
+        remove: Vec<String>,
     },
     // Set Paloma address of a chain
     SetPaloma {
@@ -82,21 +182,70 @@ pub enum ExecuteMsg {
 #[cw_serde]
 pub enum PalomaMsg {
     /// Message struct for cross-chain calls.
-    SchedulerMsg {
-        execute_job: ExecuteJob,
-    },
-    SkywayMsg {
-        set_erc20_to_denom: SetErc20ToDenom,
-    },
-    TokenFactoryMsg {
-        change_admin: ChangeAdminMsg,
-    },
+    SchedulerMsg(SchedulerMsg),
+    SkywayMsg(SkywayMsg),
+    TokenFactoryMsg(TokenFactoryMsg),
+}
+
+#[cw_serde]
+pub enum SchedulerMsg {
+    ExecuteJob(ExecuteJob),
+    CreateJob(CreateJob),
+}
+
+#[cw_serde]
+pub enum SkywayMsg {
+    SetErc20ToDenom(SetErc20ToDenom),
+    // Bridges the coins in `amount` to an EVM address through the ERC20 mapped to their denom
+    SendTx(SendTx),
+    // Cancels an outgoing Skyway transfer not batched yet, refunding the sender
+    CancelTx(CancelTx),
+}
+
+#[cw_serde]
+pub enum TokenFactoryMsg {
+    CreateDenom(CreateDenomMsg),
+    MintTokens(MintMsg),
+    BurnTokens(BurnMsg),
+    ChangeAdmin(ChangeAdminMsg),
+    SetDenomMetadata(SetDenomMetadataMsg),
 }
 
 #[cw_serde]
 pub struct ExecuteJob {
     pub job_id: String,
     pub payload: Binary,
+    // Scheduler hints, left out of the message when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+}
+
+#[cw_serde]
+pub struct CreateJob {
+    pub job_id: String,
+    pub chain_type: String,
+    pub chain_reference_id: String,
+    // JSON of the contract address and ABI the job calls
+    pub definition: String,
+    // Hex encoded default payload
+    pub payload: String,
+    pub payload_modifiable: bool,
+    pub is_multi_owned: bool,
+}
+
+#[cw_serde]
+pub struct SendTx {
+    pub remote_chain_destination_address: String,
+    // Coin string, e.g. "1000factory/paloma1.../upusd"
+    pub amount: String,
+    pub chain_reference_id: String,
+}
+
+#[cw_serde]
+pub struct CancelTx {
+    pub transaction_id: u64,
 }
 
 #[cw_serde]
@@ -143,6 +292,90 @@ pub struct ChangeAdminMsg {
     pub new_admin_address: String,
 }
 
+#[cw_serde]
+pub struct SetDenomMetadataMsg {
+    pub denom: String,
+    pub metadata: Metadata,
+}
+
+#[cw_serde]
+pub enum PalomaQuery {
+    Scheduler(SchedulerQuery),
+    Skyway(SkywayQuery),
+    TokenFactory(TokenFactoryQuery),
+    Evm(EvmQuery),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum SchedulerQuery {
+    #[returns(JobResponse)]
+    GetJobById { job_id: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum SkywayQuery {
+    // ERC20 mappings of the PUSD denom, one per chain
+    #[returns(Erc20ToDenomsResponse)]
+    GetErc20ToDenoms { denom: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum TokenFactoryQuery {
+    #[returns(FullDenomResponse)]
+    FullDenom {
+        creator_addr: String,
+        subdenom: String,
+    },
+    #[returns(DenomAdminResponse)]
+    GetDenomAdmin { denom: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum EvmQuery {
+    #[returns(ChainInfoResponse)]
+    GetChainInfo { chain_reference_id: String },
+}
+
+#[cw_serde]
+pub struct JobResponse {
+    pub job: Job,
+}
+
+#[cw_serde]
+pub struct Job {
+    pub id: String,
+    pub owner: String,
+    pub chain_type: String,
+    pub chain_reference_id: String,
+    pub is_payload_modifiable: bool,
+}
+
+#[cw_serde]
+pub struct Erc20ToDenomsResponse {
+    pub erc20_to_denoms: Vec<SetErc20ToDenom>,
+}
+
+#[cw_serde]
+pub struct FullDenomResponse {
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct DenomAdminResponse {
+    pub admin: String,
+}
+
+#[cw_serde]
+pub struct ChainInfoResponse {
+    pub chain_reference_id: String,
+    // Inactive chains are not relayed to, e.g. while validators lack a healthy RPC
+    pub active: bool,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -155,6 +388,9 @@ pub enum QueryMsg {
     #[returns(String)]
     GetJobId { chain_id: String },
 
+    #[returns(Vec<ChainSettingChange>)]
+    GetChainSettingHistory { chain_id: String },
+
     #[returns(Vec<(u64, BurnInfo)>)]
     GetWithdrawList {},
 
@@ -164,15 +400,219 @@ pub enum QueryMsg {
     #[returns(bool)]
     ReWithdrawable {},
 
+    // Pending nonces of a chain past its expiry window, not refunded yet
+    #[returns(ExpiredWithdrawalsResponse)]
+    GetExpiredWithdrawals {
+        chain_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<ExpiredWithdrawal>)]
+    GetExpiredWithdrawal { nonce: u64 },
+
     #[returns(BalanceResponse)]
     PusdBalance {},
+
+    #[returns(SolvencyResponse)]
+    Solvency {},
+
+    #[returns(Uint128)]
+    GetAllowance {
+        owner: String,
+        spender: String,
+        chain_id: String,
+    },
+
+    #[returns(ChainAccounting)]
+    GetChainAccounting { chain_id: String },
+
+    // Versioned bundle of the state with the first page of entries
+    #[returns(StateExport)]
+    ExportState { limit: Option<u32> },
+
+    // A further page of entries of an export
+    #[returns(ExportPage)]
+    ExportEntries {
+        start_after: ExportCursor,
+        limit: Option<u32>,
+    },
+
+    // Outflow of a chain next to its pending withdrawals
+    #[returns(ReconciliationResponse)]
+    Reconciliation { chain_id: String },
+
+    // Quota usage of an address on a chain over the rolling day
+    #[returns(UserQuotaResponse)]
+    UserQuota { address: String, chain_id: String },
+
+    #[returns(Vec<(u64, QueuedAdminAction)>)]
+    GetQueuedAdminActions {},
+
+    #[returns(Option<MintApprovalConfig>)]
+    GetMintApprovalConfig {},
+
+    #[returns(MintRequest)]
+    GetMintRequest { id: u64 },
+
+    // Requests that are neither executed nor expired
+    #[returns(Vec<(u64, MintRequest)>)]
+    GetOpenMintRequests {},
+
+    // Redemptions in id order, optionally of one minter
+    #[returns(Vec<(u64, Redemption)>)]
+    GetRedemptions {
+        minter: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<(u64, Redemption)>)]
+    GetRedemptionByReference { reference: String },
+
+    #[returns(RedemptionTotals)]
+    GetRedemptionTotals { minter: String },
+
+    #[returns(RemoteConfig)]
+    GetRemoteConfig { chain_id: String },
+
+    // Screening result of a Cosmos account and/or an EVM recipient
+    #[returns(ComplianceStatusResponse)]
+    ComplianceStatus {
+        sender: Option<String>,
+        recipient: Option<String>,
+    },
+
+    #[returns(Vec<String>)]
+    GetComplianceList { list: ComplianceList },
+
+    #[returns(BridgeResponse)]
+    GetBridge { chain_reference_id: String },
+
+    #[returns(Vec<(String, BridgeInfo)>)]
+    ListBridges {},
 }
 
 #[cw_serde]
 pub struct ChainSettingInfo {
     pub chain_id: String,
     pub job_id: String,
+    pub admin_job_id: Option<String>,
     pub minimum_amount: Uint128,
+    pub decimals: Option<u8>,
+    pub allowlist_only: bool,
+    pub transport: Transport,
+}
+
+#[cw_serde]
+pub struct StateExport {
+    // Format of the bundle
+    pub version: u32,
+    pub contract_version: String,
+    pub state: State,
+    pub accounting: Accounting,
+    pub counters: ExportCounters,
+    pub chain_settings: Vec<(String, ChainSetting)>,
+    pub chain_accounting: Vec<(String, ChainAccounting)>,
+    pub bridges: Vec<(String, BridgeInfo)>,
+    pub remote_configs: Vec<(String, RemoteConfig)>,
+    pub mint_approval_config: Option<MintApprovalConfig>,
+    pub queued_admin_actions: Vec<(u64, QueuedAdminAction)>,
+    pub redemption_totals: Vec<(String, RedemptionTotals)>,
+    // Number of entries over all pages
+    pub entry_count: u64,
+    // First page of entries
+    pub entries: Vec<ExportEntry>,
+    // start_after of the next page, None on the last page
+    pub next_start_after: Option<ExportCursor>,
+}
+
+#[cw_serde]
+pub struct ExportPage {
+    pub entries: Vec<ExportEntry>,
+    // start_after of the next page, None on the last page
+    pub next_start_after: Option<ExportCursor>,
+}
+
+// Entries of maps that can grow without bound, exported in this order over pages
+#[cw_serde]
+pub enum ExportEntry {
+    Withdrawal {
+        nonce: u64,
+        burn_info: BurnInfo,
+    },
+    ComplianceMember {
+        list: ComplianceList,
+        member: String,
+        added_at: Timestamp,
+    },
+    Allowance {
+        owner: String,
+        spender: String,
+        chain_id: String,
+        amount: Uint128,
+    },
+    MintRequest {
+        id: u64,
+        mint_request: MintRequest,
+    },
+    Redemption {
+        id: u64,
+        redemption: Redemption,
+    },
+}
+
+// Key of the last entry of an export page
+#[cw_serde]
+pub enum ExportCursor {
+    Withdrawal(u64),
+    ComplianceMember(ComplianceList, String),
+    Allowance(String, String, String),
+    MintRequest(u64),
+    Redemption(u64),
+}
+
+#[cw_serde]
+pub struct ExportCounters {
+    pub last_admin_action_id: u64,
+    pub last_mint_request_id: u64,
+    pub last_redemption_id: u64,
+}
+
+#[cw_serde]
+pub struct ExpiredWithdrawalsResponse {
+    pub nonces: Vec<u64>,
+    // Nonce to continue after when the scan stopped early, None once the chain is scanned
+    pub next_start_after: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ReconciliationResponse {
+    pub chain_id: String,
+    pub total_withdrawn: Uint128,
+    // Completed by BurnPusd
+    pub total_burned: Uint128,
+    pub total_cancelled: Uint128,
+    pub pending_amount: Uint128,
+    pub pending_count: u64,
+    pub oldest_pending_nonce: Option<u64>,
+    // Seconds since the oldest pending withdraw was made
+    pub oldest_pending_age: Option<u64>,
+    // Whether the withdrawn total equals burned, cancelled and pending together
+    pub balanced: bool,
+}
+
+#[cw_serde]
+pub struct UserQuotaResponse {
+    // Allowlisted accounts are not limited
+    pub exempt: bool,
+    pub used_amount: Uint128,
+    pub used_count: u32,
+    // None when the chain sets no limit
+    pub remaining_amount: Option<Uint128>,
+    pub remaining_count: Option<u32>,
+    // When the oldest counted usage leaves the rolling day, now without usage
+    pub resets_at: Timestamp,
 }
 
 #[cw_serde]
@@ -182,9 +622,44 @@ pub struct SetErc20ToDenom {
     pub chain_reference_id: String,
 }
 
+#[cw_serde]
+pub struct BridgeResponse {
+    pub chain_reference_id: String,
+    pub bridge: BridgeInfo,
+    // Previous and current mappings, oldest first
+    pub history: Vec<BridgeInfo>,
+}
+
+#[cw_serde]
+pub struct ComplianceStatusResponse {
+    pub sender_denylisted: bool,
+    pub sender_allowlisted: bool,
+    pub recipient_denylisted: bool,
+}
+
 #[cw_serde]
 pub struct BalanceResponse {
     pub balance: Uint128,
 }
 
+#[cw_serde]
+pub struct SolvencyResponse {
+    // PUSD bank balance of the contract
+    pub balance: Uint128,
+    pub total_escrowed: Uint128,
+    pub keeper_pool: Uint128,
+    // Pending Skyway withdrawals, held by Skyway rather than the contract
+    pub total_in_transit: Uint128,
+    // Balance not backing pending withdrawals or the keeper pool. The minter funds the inventory with
+    // plain bank transfers, so it is derived from the balance rather than counted.
+    pub mint_inventory: Uint128,
+    pub total_minted: Uint128,
+    pub total_burned: Uint128,
+    pub total_unminted: Uint128,
+    // Whether the balance covers all pending withdrawals and the keeper pool
+    pub solvent: bool,
+}
+
 impl CustomMsg for PalomaMsg {}
+
+impl CustomQuery for PalomaQuery {}
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Empty, Timestamp, Uint128, Uint256};
 use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -11,6 +11,16 @@ pub struct State {
     pub minter: Addr,
     pub denom: String,
     pub last_nonce: u64,
+    // Decimals of the PUSD denom on Paloma
+    #[serde(default = "default_decimals")]
+    pub decimals: u8,
+    // Seconds a queued admin action waits before it can be executed, 0 disables the queue
+    #[serde(default)]
+    pub timelock_delay: u64,
+}
+
+pub fn default_decimals() -> u8 {
+    6
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -20,15 +30,378 @@ pub struct BurnInfo {
     pub recipient: String,
     pub amount: u128,
     pub timestamp: Timestamp,
+    // Amount encoded in the withdraw payload, scaled to the remote token decimals
+    #[serde(default)]
+    pub remote_amount: Option<Uint256>,
+    // Spender that withdrew on behalf of the burner
+    #[serde(default)]
+    pub operator: Option<Addr>,
+    // Receives refunds instead of the burner
+    #[serde(default)]
+    pub refund_address: Option<Addr>,
+    // Time of the original withdraw, `timestamp` moves with every retry
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+    // Number of ReWithdraw dispatches
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub status: WithdrawStatus,
+    // Transport of the chain at the time of the withdraw
+    #[serde(default)]
+    pub transport: Transport,
+    // Outgoing Skyway transfer, set once the send is confirmed
+    #[serde(default)]
+    pub skyway_tx_id: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawStatus {
+    #[default]
+    Pending,
+    // Max retry attempts reached, ReWithdraw is refused until the owner resets the retries
+    NeedsReview,
+}
+
+impl BurnInfo {
+    pub fn created_at(&self) -> Timestamp {
+        self.created_at.unwrap_or(self.timestamp)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ExpiredWithdrawal {
+    pub burn_info: BurnInfo,
+    pub refunded_at: Timestamp,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct ChainSetting {
     pub job_id: String,
     pub minimum_amount: Uint128,
+    // Decimals of the ERC20 token on the chain, defaults to the PUSD denom decimals
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    // Only accounts on the allowlist may withdraw to the chain
+    #[serde(default)]
+    pub allowlist_only: bool,
+    // Seconds after which a pending withdraw can be refunded by anyone, None never expires
+    #[serde(default)]
+    pub expiry_window: Option<u64>,
+    // Backoff of ReWithdraw, None keeps the flat retry delay without attempt limit
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    // Paid from the keeper pool for every job dispatched by RetryBatch
+    #[serde(default)]
+    pub keeper_reward: Option<Uint128>,
+    // Job running set_paloma and the Vyper config updates, defaults to job_id
+    #[serde(default)]
+    pub admin_job_id: Option<String>,
+    // Passed to the scheduler with every job of the chain
+    #[serde(default)]
+    pub job_hints: Option<JobHints>,
+    // How withdrawals reach the chain
+    #[serde(default)]
+    pub transport: Transport,
+    // Limits per burner over a rolling day, allowlisted accounts are exempt
+    #[serde(default)]
+    pub quota: Option<Quota>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Quota {
+    // Amount a burner may withdraw to the chain per day, None is unlimited
+    pub max_amount: Option<Uint128>,
+    // Number of withdrawals a burner may make to the chain per day, None is unlimited
+    pub max_count: Option<u32>,
+}
+
+// Quotas count the usage of the last QUOTA_BUCKETS buckets of QUOTA_BUCKET seconds,
+// a rolling day
+pub const QUOTA_BUCKET: u64 = 3_600;
+pub const QUOTA_BUCKETS: u64 = 24;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct QuotaUsage {
+    pub amount: Uint128,
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    // Withdraw job of the Compass contract, escrowing PUSD until BurnPusd
+    #[default]
+    Compass,
+    // Skyway send_tx through the ERC20 mapped by SetBridge, PUSD leaves the contract
+    Skyway,
+}
+
+impl Transport {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transport::Compass => "compass",
+            Transport::Skyway => "skyway",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct JobHints {
+    pub gas_limit: Option<u64>,
+    pub priority: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RetryPolicy {
+    // Delay before the first retry, in seconds
+    pub base_delay: u64,
+    // Factor applied to the delay after every retry
+    pub multiplier: u32,
+    pub max_delay: u64,
+    pub max_attempts: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainSettingAction {
+    Register,
+    Update,
+    Deregister,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ChainSettingChange {
+    pub action: ChainSettingAction,
+    // Setting after the change, None when the chain is deregistered
+    pub chain_setting: Option<ChainSetting>,
+    pub sender: Addr,
+    pub timestamp: Timestamp,
+}
+
+// Last settings issued to the Vyper contract of a chain
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct RemoteConfig {
+    pub compass: Option<String>,
+    pub refund_wallet: Option<String>,
+    pub redemption_fee: Option<Uint128>,
+    pub paloma_set: bool,
+    pub updated_at: Option<Timestamp>,
+}
+
+// Sensitive admin actions that are queued behind the timelock delay
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    RegisterChain {
+        chain_id: String,
+        chain_setting: ChainSetting,
+    },
+    UpdateChain {
+        chain_id: String,
+        chain_setting: ChainSetting,
+    },
+    DeregisterChain {
+        chain_id: String,
+        force: Option<bool>,
+    },
+    UpdateCompass {
+        chain_id: String,
+        new_compass: String,
+    },
+    UpdateRefundWallet {
+        chain_id: String,
+        new_refund_wallet: String,
+    },
+    UpdateRedemptionFee {
+        chain_id: String,
+        new_redemption_fee: Uint128,
+    },
+    UpdateOwner {
+        owner: Addr,
+    },
+    UpdateMintApprovalConfig {
+        config: Option<MintApprovalConfig>,
+    },
+    UpdateTimelockDelay {
+        timelock_delay: u64,
+    },
+}
+
+impl AdminAction {
+    // Snake case name, as used in the serialized form
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdminAction::RegisterChain { .. } => "register_chain",
+            AdminAction::UpdateChain { .. } => "update_chain",
+            AdminAction::DeregisterChain { .. } => "deregister_chain",
+            AdminAction::UpdateCompass { .. } => "update_compass",
+            AdminAction::UpdateRefundWallet { .. } => "update_refund_wallet",
+            AdminAction::UpdateRedemptionFee { .. } => "update_redemption_fee",
+            AdminAction::UpdateOwner { .. } => "update_owner",
+            AdminAction::UpdateMintApprovalConfig { .. } => "update_mint_approval_config",
+            AdminAction::UpdateTimelockDelay { .. } => "update_timelock_delay",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct QueuedAdminAction {
+    pub action: AdminAction,
+    pub proposer: Addr,
+    pub queued_at: Timestamp,
+    // Earliest time the action can be executed
+    pub eta: Timestamp,
+}
+
+// M-of-N approval required for mints of at least minimum_amount
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MintApprovalConfig {
+    pub proposers: Vec<Addr>,
+    pub approvers: Vec<Addr>,
+    pub threshold: u32,
+    pub minimum_amount: Uint128,
+    // Seconds a mint request stays open for approvals
+    pub expiry: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MintRequest {
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub proposer: Addr,
+    pub approvals: Vec<Addr>,
+    pub created_at: Timestamp,
+    pub expires_at: Timestamp,
+    pub executed: bool,
+}
+
+// PUSD taken out of the inventory by the minter with UnmintPusd
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Redemption {
+    pub minter: Addr,
+    pub amount: Uint128,
+    // Off-chain collateral record the redemption settles
+    pub reference: Option<String>,
+    // Burned by the contract instead of sent to the minter
+    pub burned: bool,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct RedemptionTotals {
+    // Amount sent to the minter
+    pub total_transferred: Uint128,
+    pub total_burned: Uint128,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct Accounting {
+    // Amount of pending withdrawals held in escrow
+    pub total_escrowed: Uint128,
+    pub total_minted: Uint128,
+    // Amount of withdrawals completed by BurnPusd
+    pub total_burned: Uint128,
+    pub total_unminted: Uint128,
+    // Funded by FundKeeperPool, paid out as RetryBatch rewards
+    #[serde(default)]
+    pub keeper_pool: Uint128,
+    // Amount of pending Skyway withdrawals, held by Skyway until relayed or cancelled
+    #[serde(default)]
+    pub total_in_transit: Uint128,
+}
+
+// Outflow to a chain
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct ChainAccounting {
+    pub total_withdrawn: Uint128,
+    pub total_burned: Uint128,
+    // Amount refunded by CancelWithdraw, RefundExpired and ForceRefund
+    #[serde(default)]
+    pub total_cancelled: Uint128,
 }
 
 pub const TX_TIMESTAMP: Map<(u64, String), Timestamp> = Map::new("tx_timestamp");
 pub const CHAIN_SETTINGS: Map<String, ChainSetting> = Map::new("chain_settings");
+// Audit trail of chain setting changes, keyed by chain_id and a per-chain sequence number
+pub const CHAIN_SETTING_HISTORY: Map<(String, u64), ChainSettingChange> =
+    Map::new("chain_setting_history");
+pub const REMOTE_CONFIGS: Map<String, RemoteConfig> = Map::new("remote_configs");
+pub const QUEUED_ADMIN_ACTIONS: Map<u64, QueuedAdminAction> = Map::new("queued_admin_actions");
+pub const LAST_ADMIN_ACTION_ID: Item<u64> = Item::new("last_admin_action_id");
+// Set while an instantiation from an export waits for its remaining entries
+pub const IMPORT_PROGRESS: Item<ImportProgress> = Item::new("import_progress");
+pub const MINT_APPROVAL_CONFIG: Item<MintApprovalConfig> = Item::new("mint_approval_config");
+pub const MINT_REQUESTS: Map<u64, MintRequest> = Map::new("mint_requests");
+pub const LAST_MINT_REQUEST_ID: Item<u64> = Item::new("last_mint_request_id");
+pub const REDEMPTIONS: Map<u64, Redemption> = Map::new("redemptions");
+pub const LAST_REDEMPTION_ID: Item<u64> = Item::new("last_redemption_id");
+// Redemption id of each reference, references can't be reused
+pub const REDEMPTION_REFERENCES: Map<String, u64> = Map::new("redemption_references");
+pub const REDEMPTION_TOTALS: Map<String, RedemptionTotals> = Map::new("redemption_totals");
+pub const ACCOUNTING: Item<Accounting> = Item::new("accounting");
+pub const CHAIN_ACCOUNTING: Map<String, ChainAccounting> = Map::new("chain_accounting");
 pub const STATE: Item<State> = Item::new("state");
 pub const WITHDRAW_LIST: Map<u64, BurnInfo> = Map::new("burn_list");
+// Nonces of the pending withdrawals of each chain, keyed by (chain_id, nonce)
+pub const CHAIN_WITHDRAWALS: Map<(String, u64), Empty> = Map::new("chain_withdrawals");
+// Withdrawals refunded by RefundExpired, keyed by nonce
+pub const EXPIRED_WITHDRAWALS: Map<u64, ExpiredWithdrawal> = Map::new("expired_withdrawals");
+// Amount a spender may withdraw on behalf of an owner, keyed by (owner, spender, chain_id)
+pub const ALLOWANCES: Map<(String, String, String), Uint128> = Map::new("allowances");
+// Withdrawals of a burner to a chain per quota bucket, keyed by (burner, chain_id, bucket)
+// where bucket is the block time divided by QUOTA_BUCKET
+pub const QUOTA_USAGE: Map<(String, String, u64), QuotaUsage> = Map::new("quota_usage");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ImportProgress {
+    // Entry count of the export
+    pub expected_entries: u64,
+    pub imported_entries: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BridgeInfo {
+    pub erc20_address: String,
+    pub timestamp: Timestamp,
+}
+
+// Compliance lists, each member is stored with the time it was added
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceList {
+    // Cosmos accounts that can't withdraw or receive mints
+    SenderDenylist,
+    // EVM addresses, stored lowercase, that can't receive withdrawals
+    RecipientDenylist,
+    // Cosmos accounts that can withdraw to chains in allowlist only mode
+    Allowlist,
+}
+
+impl ComplianceList {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComplianceList::SenderDenylist => "sender_denylist",
+            ComplianceList::RecipientDenylist => "recipient_denylist",
+            ComplianceList::Allowlist => "allowlist",
+        }
+    }
+
+    pub fn members(&self) -> Map<String, Timestamp> {
+        match self {
+            ComplianceList::SenderDenylist => SENDER_DENYLIST,
+            ComplianceList::RecipientDenylist => RECIPIENT_DENYLIST,
+            ComplianceList::Allowlist => ALLOWLIST,
+        }
+    }
+}
+
+pub const SENDER_DENYLIST: Map<String, Timestamp> = Map::new("sender_denylist");
+pub const RECIPIENT_DENYLIST: Map<String, Timestamp> = Map::new("recipient_denylist");
+pub const ALLOWLIST: Map<String, Timestamp> = Map::new("allowlist");
+
+pub const BRIDGES: Map<String, BridgeInfo> = Map::new("bridges");
+// Every SetBridge call per chain_reference_id, keyed by a per-chain sequence number
+pub const BRIDGE_HISTORY: Map<(String, u64), BridgeInfo> = Map::new("bridge_history");